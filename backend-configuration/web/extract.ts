@@ -579,6 +579,329 @@ fs.readdirSync(cssDir).forEach((fileName) => {
   }
 })
 
+// extract style functions
+// the functions are curated (with their parameters) since MDN does not list parameters in a uniform way
+type StyleFunction = {
+  name: string
+  relPath: string
+  description: string
+  parameters: { name: string; description: string }[]
+}
+const styleFunctions: StyleFunction[] = [
+  {
+    name: 'calc',
+    relPath: 'calc',
+    description:
+      'The **`calc()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function lets you perform calculations when specifying CSS property values.',
+    parameters: [
+      {
+        name: 'expression',
+        description: 'A mathematical expression, the result of which is used as the value.',
+      },
+    ],
+  },
+  {
+    name: 'clamp',
+    relPath: 'clamp',
+    description:
+      'The **`clamp()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function clamps a middle value within a range of values between a defined minimum bound and a maximum bound.',
+    parameters: [
+      {
+        name: 'min',
+        description: 'The minimum value. This is the lower bound in the range of allowed values.',
+      },
+      { name: 'val', description: 'The preferred value.' },
+      {
+        name: 'max',
+        description: 'The maximum value. This is the upper bound in the range of allowed values.',
+      },
+    ],
+  },
+  {
+    name: 'cubic-bezier',
+    relPath: 'easing-function/cubic-bezier',
+    description:
+      'The **`cubic-bezier()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function creates a smooth transition using a cubic Bézier curve.',
+    parameters: [
+      {
+        name: 'x1',
+        description:
+          'The x-axis coordinate of the first control point. It must be in the `[0, 1]` range.',
+      },
+      { name: 'y1', description: 'The y-axis coordinate of the first control point.' },
+      {
+        name: 'x2',
+        description:
+          'The x-axis coordinate of the second control point. It must be in the `[0, 1]` range.',
+      },
+      { name: 'y2', description: 'The y-axis coordinate of the second control point.' },
+    ],
+  },
+  {
+    name: 'env',
+    relPath: 'env',
+    description:
+      'The **`env()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function can be used to insert the value of a user-agent defined environment variable into your CSS.',
+    parameters: [
+      {
+        name: 'environment-variable',
+        description: 'The name of the environment variable, e.g. `safe-area-inset-top` .',
+      },
+      {
+        name: 'fallback',
+        description: 'The value to use if the environment variable is not available.',
+      },
+    ],
+  },
+  {
+    name: 'hsl',
+    relPath: 'color_value/hsl',
+    description:
+      'The **`hsl()`** functional notation expresses a color according to its *hue*, *saturation*, and *lightness* components.',
+    parameters: [
+      { name: 'H', description: 'The hue angle of the color.' },
+      { name: 'S', description: 'The saturation of the color as a percentage.' },
+      { name: 'L', description: 'The lightness of the color as a percentage.' },
+      {
+        name: 'A',
+        description:
+          'The alpha channel value of the color, in the range `[0, 1]` or as a percentage.',
+      },
+    ],
+  },
+  {
+    name: 'hsla',
+    relPath: 'color_value/hsla',
+    description: 'The **`hsla()`** functional notation is an alias for `hsl()` .',
+    parameters: [
+      { name: 'H', description: 'The hue angle of the color.' },
+      { name: 'S', description: 'The saturation of the color as a percentage.' },
+      { name: 'L', description: 'The lightness of the color as a percentage.' },
+      {
+        name: 'A',
+        description:
+          'The alpha channel value of the color, in the range `[0, 1]` or as a percentage.',
+      },
+    ],
+  },
+  {
+    name: 'linear-gradient',
+    relPath: 'gradient/linear-gradient',
+    description:
+      'The **`linear-gradient()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function creates an image consisting of a progressive transition between two or more colors along a straight line.',
+    parameters: [
+      {
+        name: 'angle',
+        description:
+          "The gradient line's angle of direction, or a `to` keyword followed by sides or corners.",
+      },
+      {
+        name: 'color-stop',
+        description: 'A color, followed by one or two optional stop positions.',
+      },
+      {
+        name: 'color-stop',
+        description: 'Another color, followed by one or two optional stop positions.',
+      },
+    ],
+  },
+  {
+    name: 'max',
+    relPath: 'max',
+    description:
+      'The **`max()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function lets you set the largest (most positive) value from a list of comma-separated expressions as the value of a CSS property value.',
+    parameters: [
+      { name: 'value1', description: 'A value or an expression to compare.' },
+      { name: 'value2', description: 'Another value or expression to compare.' },
+    ],
+  },
+  {
+    name: 'min',
+    relPath: 'min',
+    description:
+      'The **`min()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function lets you set the smallest (most negative) value from a list of comma-separated expressions as the value of a CSS property value.',
+    parameters: [
+      { name: 'value1', description: 'A value or an expression to compare.' },
+      { name: 'value2', description: 'Another value or expression to compare.' },
+    ],
+  },
+  {
+    name: 'radial-gradient',
+    relPath: 'gradient/radial-gradient',
+    description:
+      'The **`radial-gradient()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function creates an image consisting of a progressive transition between two or more colors that radiate from an origin.',
+    parameters: [
+      {
+        name: 'ending-shape',
+        description: 'The shape, size and position of the gradient, e.g. `circle at center` .',
+      },
+      {
+        name: 'color-stop',
+        description: 'A color, followed by one or two optional stop positions.',
+      },
+      {
+        name: 'color-stop',
+        description: 'Another color, followed by one or two optional stop positions.',
+      },
+    ],
+  },
+  {
+    name: 'rgb',
+    relPath: 'color_value/rgb',
+    description:
+      'The **`rgb()`** functional notation expresses a color according to its red, green, and blue components.',
+    parameters: [
+      {
+        name: 'R',
+        description:
+          'The red channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'G',
+        description:
+          'The green channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'B',
+        description:
+          'The blue channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'A',
+        description:
+          'The alpha channel value of the color, in the range `[0, 1]` or as a percentage.',
+      },
+    ],
+  },
+  {
+    name: 'rgba',
+    relPath: 'color_value/rgba',
+    description: 'The **`rgba()`** functional notation is an alias for `rgb()` .',
+    parameters: [
+      {
+        name: 'R',
+        description:
+          'The red channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'G',
+        description:
+          'The green channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'B',
+        description:
+          'The blue channel value of the color, in the range `[0, 255]` or as a percentage.',
+      },
+      {
+        name: 'A',
+        description:
+          'The alpha channel value of the color, in the range `[0, 1]` or as a percentage.',
+      },
+    ],
+  },
+  {
+    name: 'rotate',
+    relPath: 'transform-function/rotate',
+    description:
+      'The **`rotate()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function defines a transformation that rotates an element around a fixed point on the 2D plane, without deforming it.',
+    parameters: [
+      {
+        name: 'a',
+        description:
+          'An angle representing the angle of rotation. A positive angle denotes a clockwise rotation.',
+      },
+    ],
+  },
+  {
+    name: 'scale',
+    relPath: 'transform-function/scale',
+    description:
+      'The **`scale()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function defines a transformation that resizes an element on the 2D plane.',
+    parameters: [
+      {
+        name: 'sx',
+        description: 'A number or percentage representing the abscissa of the scaling vector.',
+      },
+      {
+        name: 'sy',
+        description:
+          'A number or percentage representing the ordinate of the scaling vector. Defaults to `sx` .',
+      },
+    ],
+  },
+  {
+    name: 'steps',
+    relPath: 'easing-function/steps',
+    description:
+      'The **`steps()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function divides the input time into a specified number of intervals that are equal in length.',
+    parameters: [
+      {
+        name: 'number-of-steps',
+        description: 'A strictly positive integer, representing the equidistant intervals.',
+      },
+      {
+        name: 'direction',
+        description: 'A keyword indicating when the jumps occur, e.g. `jump-start` or `jump-end` .',
+      },
+    ],
+  },
+  {
+    name: 'translate',
+    relPath: 'transform-function/translate',
+    description:
+      'The **`translate()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function repositions an element in the horizontal and/or vertical directions.',
+    parameters: [
+      {
+        name: 'tx',
+        description: 'A length or percentage representing the abscissa of the translating vector.',
+      },
+      {
+        name: 'ty',
+        description:
+          'A length or percentage representing the ordinate of the translating vector. Defaults to `0` .',
+      },
+    ],
+  },
+  {
+    name: 'var',
+    relPath: 'var',
+    description:
+      'The **`var()`** [CSS](https://developer.mozilla.org/en-US/docs/Web/CSS) function can be used to insert the value of a custom property (sometimes called a "CSS variable") instead of any part of a value of another property.',
+    parameters: [
+      {
+        name: 'custom-property-name',
+        description:
+          "The custom property's name represented by an identifier that starts with two dashes.",
+      },
+      {
+        name: 'declaration-value',
+        description:
+          "The custom property's fallback value, which is used in case the custom property is not defined.",
+      },
+    ],
+  },
+]
+fs.writeSync(outFile, `\n`)
+for (const func of styleFunctions) {
+  if (!fs.existsSync(path.join(cssDir, func.relPath, 'index.md'))) {
+    console.warn(`Cannot find style function "${func.name}" in MDN.`)
+  }
+  console.info(`Style Function: ${func.name}`)
+  fs.writeSync(outFile, '[[style-function]]\n')
+  fs.writeSync(outFile, `name = "${func.name}"\n`)
+  writeDescriptionLine(func.description)
+  const reference = `https://developer.mozilla.org/en-US/web/css/${func.relPath}`
+  fs.writeSync(outFile, `reference = "${reference}"\n`)
+  fs.writeSync(outFile, `\n`)
+  for (const param of func.parameters) {
+    fs.writeSync(outFile, '[[style-function.parameter]]\n')
+    fs.writeSync(outFile, `name = "${param.name}"\n`)
+    writeDescriptionLine(param.description)
+    fs.writeSync(outFile, `\n`)
+  }
+}
+
 // finish
 fs.closeSync(outFile)
 console.info('Done!')
@@ -0,0 +1,388 @@
+use std::{collections::HashMap, ops::Range, path::Path};
+
+use compact_str::CompactString;
+use glass_easel_template_compiler::parse::{
+    tag::{Element, ElementKind, Ident, Node, NormalAttribute, Value},
+    Position,
+};
+use itertools::Itertools;
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::{
+    context::{backend_configuration::BackendConfig, project::Project, FileLang},
+    json_utils::{for_each_json_entry, JsonEntryLocation},
+    utils::location_to_lsp_range,
+    wxml_utils::for_each_template_element,
+};
+
+/// Collect the diagnostics which require cross-file or backend information.
+///
+/// Parsing errors are not included.
+pub(crate) fn check_file(
+    project: &Project,
+    backend_config: &BackendConfig,
+    abs_path: &Path,
+) -> Vec<Diagnostic> {
+    let file_lang = project
+        .cached_file_content(abs_path)
+        .map(|x| x.file_lang)
+        .unwrap_or(FileLang::Unknown);
+    match file_lang {
+        FileLang::Wxml => check_wxml(project, backend_config, abs_path),
+        FileLang::Json => check_json(project, backend_config, abs_path),
+        _ => vec![],
+    }
+}
+
+fn check_wxml(
+    project: &Project,
+    backend_config: &BackendConfig,
+    abs_path: &Path,
+) -> Vec<Diagnostic> {
+    let mut ret = vec![];
+    let Ok(template) = project.get_wxml_tree(abs_path) else {
+        return ret;
+    };
+    for_each_template_element(template, |elem, _| {
+        let ElementKind::Normal {
+            tag_name,
+            attributes,
+            generics,
+            ..
+        } = &elem.kind
+        else {
+            return;
+        };
+        if let Some(script) = project.get_target_component_script(abs_path, &tag_name.name) {
+            if script.is_complete {
+                for attr in attributes.iter() {
+                    let name = attr.name.name.as_str();
+                    if script.find_property(name).is_some()
+                        || script.external_classes.contains(&name)
+                        || backend_config
+                            .global_attribute
+                            .iter()
+                            .any(|x| x.name == name)
+                    {
+                        continue;
+                    }
+                    ret.push(Diagnostic {
+                        range: location_to_lsp_range(&attr.name.location),
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        message: format!(
+                            "property `{}` is not defined in component <{}>",
+                            name, tag_name.name
+                        ),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        if project
+            .get_target_component_path(abs_path, &tag_name.name)
+            .is_none()
+        {
+            check_backend_attributes(backend_config, tag_name, attributes, &mut ret);
+        }
+        let Some(target_generics) = project.get_target_component_generics(abs_path, &tag_name.name)
+        else {
+            return;
+        };
+        for (name, generic) in target_generics.iter().sorted_by_key(|x| x.0) {
+            if generic.default_component().is_some() {
+                continue;
+            }
+            if generics.iter().any(|x| x.name.name == name) {
+                continue;
+            }
+            ret.push(Diagnostic {
+                range: location_to_lsp_range(&tag_name.location),
+                severity: Some(DiagnosticSeverity::WARNING),
+                message: format!(
+                    "missing `generic:{}` which is required by component <{}>",
+                    name, tag_name.name
+                ),
+                ..Default::default()
+            });
+        }
+    });
+    check_slot_names(project, abs_path, None, &template.content, &mut ret);
+    if let Some(script) = project
+        .get_own_component_script(abs_path)
+        .filter(|x| x.is_complete)
+    {
+        for_each_template_element(template, |elem, _| {
+            let common = match &elem.kind {
+                ElementKind::Normal { common, .. } | ElementKind::Slot { common, .. } => common,
+                _ => return,
+            };
+            for ev in common.event_bindings.iter() {
+                let Some(Value::Static {
+                    value, location, ..
+                }) = ev.value.as_ref()
+                else {
+                    continue;
+                };
+                let name = value.trim();
+                if name.is_empty() || script.find_method(name).is_some() {
+                    continue;
+                }
+                ret.push(Diagnostic {
+                    range: location_to_lsp_range(location),
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    message: format!("event handler `{}` is not defined in the script", name),
+                    ..Default::default()
+                });
+            }
+        });
+    }
+    ret
+}
+
+/// Check the static attribute values and the required properties with the backend configuration.
+fn check_backend_attributes(
+    backend_config: &BackendConfig,
+    tag_name: &Ident,
+    attributes: &[NormalAttribute],
+    ret: &mut Vec<Diagnostic>,
+) {
+    let comp = backend_config.search_component(&tag_name.name);
+    for attr in attributes.iter() {
+        let Some(Value::Static {
+            value, location, ..
+        }) = attr.value.as_ref()
+        else {
+            continue;
+        };
+        let config = match comp {
+            Some(_) => backend_config.search_property(&tag_name.name, &attr.name.name),
+            None => backend_config.search_attribute(&tag_name.name, &attr.name.name),
+        };
+        let Some(config) = config else {
+            continue;
+        };
+        let Some(values) = config.ty.enum_values() else {
+            continue;
+        };
+        if values.contains(&value.as_str()) {
+            continue;
+        }
+        ret.push(Diagnostic {
+            range: location_to_lsp_range(location),
+            severity: Some(DiagnosticSeverity::WARNING),
+            message: format!(
+                "`{}` is not a valid value of `{}` (expected `{}`)",
+                value, attr.name.name, config.ty
+            ),
+            ..Default::default()
+        });
+    }
+    let Some(comp) = comp else {
+        return;
+    };
+    for prop in comp.property.iter().filter(|x| x.required) {
+        if attributes.iter().any(|x| x.name.name == prop.name) {
+            continue;
+        }
+        ret.push(Diagnostic {
+            range: location_to_lsp_range(&tag_name.location),
+            severity: Some(DiagnosticSeverity::WARNING),
+            message: format!(
+                "missing property `{}` which is required by component <{}>",
+                prop.name, tag_name.name
+            ),
+            ..Default::default()
+        });
+    }
+}
+
+/// Check the `slot` attributes of the nodes.
+///
+/// The `owner` is the nearest ancestor which is not a `wx:if`, `wx:for` or `<block>`.
+fn check_slot_names(
+    project: &Project,
+    abs_path: &Path,
+    owner: Option<&Element>,
+    nodes: &[Node],
+    ret: &mut Vec<Diagnostic>,
+) {
+    for node in nodes {
+        let Node::Element(elem) = node else {
+            continue;
+        };
+        let slot = match &elem.kind {
+            ElementKind::Normal { common, .. } | ElementKind::Slot { common, .. } => {
+                common.slot.as_ref()
+            }
+            ElementKind::Pure { slot, .. } => slot.as_ref(),
+            _ => None,
+        };
+        let owner_tag_name = owner.and_then(|x| match &x.kind {
+            ElementKind::Normal { tag_name, .. } => Some(tag_name),
+            _ => None,
+        });
+        if let (
+            Some((
+                _,
+                Value::Static {
+                    value, location, ..
+                },
+            )),
+            Some(tag_name),
+        ) = (slot, owner_tag_name)
+        {
+            if let Some(diag) = check_slot_name(project, abs_path, &tag_name.name, value, location)
+            {
+                ret.push(diag);
+            }
+        }
+        match &elem.kind {
+            ElementKind::Normal { children, .. } => {
+                check_slot_names(project, abs_path, Some(elem), children, ret);
+            }
+            ElementKind::Pure { children, .. } | ElementKind::For { children, .. } => {
+                check_slot_names(project, abs_path, owner, children, ret);
+            }
+            ElementKind::If {
+                branches,
+                else_branch,
+                ..
+            } => {
+                for (_, _, children) in branches {
+                    check_slot_names(project, abs_path, owner, children, ret);
+                }
+                if let Some((_, children)) = else_branch {
+                    check_slot_names(project, abs_path, owner, children, ret);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_slot_name(
+    project: &Project,
+    abs_path: &Path,
+    tag_name: &str,
+    slot_name: &str,
+    location: &Range<Position>,
+) -> Option<Diagnostic> {
+    if slot_name.is_empty() {
+        return None;
+    }
+    let target_path = project.get_target_component_path(abs_path, tag_name)?;
+    let slot_names = project.get_target_component_slot_names(abs_path, tag_name)?;
+    let multiple_slots = project
+        .get_component_script(&target_path)
+        .and_then(|x| x.multiple_slots);
+    let message = if multiple_slots == Some(false) {
+        format!(
+            "component <{}> does not enable `multipleSlots` so slot `{}` is ignored",
+            tag_name, slot_name
+        )
+    } else if !slot_names.contains(&slot_name) {
+        format!(
+            "slot `{}` is not found in component <{}>",
+            slot_name, tag_name
+        )
+    } else {
+        return None;
+    };
+    Some(Diagnostic {
+        range: location_to_lsp_range(location),
+        severity: Some(DiagnosticSeverity::WARNING),
+        message,
+        ..Default::default()
+    })
+}
+
+fn check_json(
+    project: &Project,
+    backend_config: &BackendConfig,
+    abs_path: &Path,
+) -> Vec<Diagnostic> {
+    let mut ret = vec![];
+    if project.get_json_config(abs_path).is_none() {
+        return ret;
+    }
+    let Some(content) = project.cached_file_content(abs_path) else {
+        return ret;
+    };
+    let mut entries: HashMap<(CompactString, CompactString), JsonEntryLocation> = HashMap::new();
+    for_each_json_entry(content, |path, loc| {
+        if let [field, key] = path {
+            if field == "usingComponents" || field == "componentPlaceholder" {
+                entries.insert((field.clone(), key.clone()), loc);
+            }
+        }
+    });
+    let entry_range = |field: &str, key: &str, is_value: bool| {
+        entries
+            .get(&(field.into(), key.into()))
+            .map(|x| location_to_lsp_range(if is_value { &x.value } else { &x.key }))
+            .unwrap_or_default()
+    };
+
+    // check placeholders
+    let using_components: HashMap<_, _> = project.iter_using_components(abs_path).collect();
+    for (tag_name, placeholder) in project
+        .iter_component_placeholders(abs_path)
+        .sorted_by_key(|x| x.0)
+    {
+        if !using_components.contains_key(tag_name) && !project.is_global_using_component(tag_name)
+        {
+            ret.push(Diagnostic {
+                range: entry_range("componentPlaceholder", tag_name, false),
+                severity: Some(DiagnosticSeverity::WARNING),
+                message: format!("`{}` is not declared in `usingComponents`", tag_name),
+                ..Default::default()
+            });
+        }
+        let is_valid_placeholder = backend_config.search_element(placeholder).is_some()
+            || backend_config.search_component(placeholder).is_some()
+            || using_components.contains_key(placeholder)
+            || project.is_global_using_component(placeholder);
+        if !is_valid_placeholder {
+            ret.push(Diagnostic {
+                range: entry_range("componentPlaceholder", tag_name, true),
+                severity: Some(DiagnosticSeverity::WARNING),
+                message: format!(
+                    "placeholder <{}> is neither a built-in component nor a custom component",
+                    placeholder
+                ),
+                ..Default::default()
+            });
+        }
+    }
+
+    // check references across subpackages
+    let self_subpackage = project.subpackage_root_for_path(abs_path);
+    for (tag_name, rel_path) in using_components.iter().sorted_by_key(|x| x.0) {
+        let Some(target_path) = project.get_target_component_path(abs_path, tag_name) else {
+            continue;
+        };
+        let Some(target_subpackage) = project.subpackage_root_for_path(&target_path) else {
+            continue;
+        };
+        if Some(target_subpackage) == self_subpackage {
+            continue;
+        }
+        if project
+            .get_component_placeholder(abs_path, tag_name)
+            .is_some()
+        {
+            continue;
+        }
+        ret.push(Diagnostic {
+            range: entry_range("usingComponents", tag_name, true),
+            severity: Some(DiagnosticSeverity::ERROR),
+            message: format!(
+                "`{}` is in subpackage `{}` and requires a `componentPlaceholder` entry for <{}>",
+                rel_path, target_subpackage, tag_name
+            ),
+            ..Default::default()
+        });
+    }
+
+    ret
+}
@@ -0,0 +1,303 @@
+use std::path::{Path, PathBuf};
+
+use super::backend_config_diagnostic_lines;
+use crate::{
+    config::{BackendConfigLayers, BackendConfigSource},
+    context::{
+        backend_configuration::{
+            AttributeConfig, BackendConfig, ComponentConfig, ElementConfig, EventConfig,
+        },
+        project::FileContentMetadata,
+        FileLang,
+    },
+};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum BackendConfigCommand {
+    /// Validate a backend configuration file, or the result of applying overlays on it
+    Validate {
+        /// The backend configuration file (TOML)
+        file: PathBuf,
+        /// The overlays applied on the backend configuration file in order
+        overlays: Vec<PathBuf>,
+    },
+    /// Print the template backend configuration (TypeScript declarations) generated from a backend configuration file
+    Template {
+        /// The backend configuration file (TOML)
+        file: PathBuf,
+    },
+    /// Compare two backend configuration files
+    Diff {
+        /// The old backend configuration file (TOML)
+        old: PathBuf,
+        /// The new backend configuration file (TOML)
+        new: PathBuf,
+    },
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| anyhow::anyhow!("failed to read {:?}: {}", path, err))
+}
+
+/// Parse a backend configuration file, printing the error with line and column if failed.
+fn parse_file(path: &Path) -> anyhow::Result<Option<BackendConfig>> {
+    let s = read_file(path)?;
+    let err = match BackendConfig::parse_str(&s) {
+        Ok(x) => return Ok(Some(x)),
+        Err(err) => err,
+    };
+    match err
+        .downcast_ref::<toml::de::Error>()
+        .and_then(|x| Some((x.span()?, x.message())))
+    {
+        Some((span, message)) => {
+            let index = span.start.min(s.len());
+            let (line, utf16_col) = FileContentMetadata::new(s, FileLang::Unknown)
+                .line_utf16_col_for_content_index(index);
+            println!(
+                "{}:{}:{}: error: {}",
+                path.display(),
+                line + 1,
+                utf16_col + 1,
+                message,
+            );
+        }
+        None => {
+            println!("{}: error: {}", path.display(), err);
+        }
+    }
+    Ok(None)
+}
+
+fn validate(path: &Path, overlays: Vec<PathBuf>) -> anyhow::Result<i32> {
+    let config = if overlays.is_empty() {
+        let Some(config) = parse_file(path)? else {
+            return Ok(1);
+        };
+        config
+    } else {
+        let layers = BackendConfigLayers {
+            base: Some(BackendConfigSource::Path(path.to_path_buf())),
+            overlays,
+        };
+        let (ret, diagnostics) = layers.load();
+        for line in backend_config_diagnostic_lines(&diagnostics) {
+            println!("{}", line);
+        }
+        match ret {
+            Ok(x) => x,
+            Err(err) => {
+                if diagnostics.iter().all(|(_, list)| list.is_empty()) {
+                    println!("error: {}", err);
+                }
+                return Ok(1);
+            }
+        }
+    };
+    let warnings = config.validate();
+    for msg in warnings.iter() {
+        println!("{}: warning: {}", path.display(), msg);
+    }
+    eprintln!(
+        "{} element(s), {} component(s), {} warning(s)",
+        config.element.len(),
+        config.component.len(),
+        warnings.len(),
+    );
+    Ok(0)
+}
+
+fn template(path: &Path) -> anyhow::Result<i32> {
+    let Some(config) = parse_file(path)? else {
+        return Ok(1);
+    };
+    print!("{}", config.generate_template_backend_config());
+    Ok(0)
+}
+
+trait DiffItem {
+    fn name(&self) -> &str;
+    fn display_name(&self) -> String;
+    fn deprecated(&self) -> bool;
+}
+
+macro_rules! impl_diff_item {
+    ($t:ty, $name:ident, $display:literal) => {
+        impl DiffItem for $t {
+            fn name(&self) -> &str {
+                &self.$name
+            }
+
+            fn display_name(&self) -> String {
+                format!($display, self.$name)
+            }
+
+            fn deprecated(&self) -> bool {
+                self.deprecated
+            }
+        }
+    };
+}
+
+impl_diff_item!(ElementConfig, tag_name, "<{}>");
+impl_diff_item!(ComponentConfig, tag_name, "<{}>");
+impl_diff_item!(AttributeConfig, name, "`{}`");
+impl_diff_item!(EventConfig, name, "`{}`");
+
+struct ConfigDiff {
+    lines: Vec<String>,
+}
+
+impl ConfigDiff {
+    fn compare_items<'a, T: DiffItem>(
+        &mut self,
+        kind: &str,
+        scope: &str,
+        old: &'a [T],
+        new: &'a [T],
+        mut nested: impl FnMut(&mut Self, &'a T, &'a T),
+    ) {
+        for item in old {
+            if !new.iter().any(|x| x.name() == item.name()) {
+                self.lines
+                    .push(format!("- {} {}{}", kind, item.display_name(), scope));
+            }
+        }
+        for item in new {
+            let Some(old_item) = old.iter().find(|x| x.name() == item.name()) else {
+                let suffix = if item.deprecated() {
+                    " (deprecated)"
+                } else {
+                    ""
+                };
+                self.lines.push(format!(
+                    "+ {} {}{}{}",
+                    kind,
+                    item.display_name(),
+                    scope,
+                    suffix
+                ));
+                continue;
+            };
+            match (old_item.deprecated(), item.deprecated()) {
+                (false, true) => self.lines.push(format!(
+                    "! {} {}{} is deprecated",
+                    kind,
+                    item.display_name(),
+                    scope
+                )),
+                (true, false) => self.lines.push(format!(
+                    "! {} {}{} is no longer deprecated",
+                    kind,
+                    item.display_name(),
+                    scope
+                )),
+                _ => {}
+            }
+            nested(self, old_item, item);
+        }
+    }
+
+    fn compare_attributes(
+        &mut self,
+        kind: &str,
+        scope: &str,
+        old: &[AttributeConfig],
+        new: &[AttributeConfig],
+    ) {
+        self.compare_items(kind, scope, old, new, |this, old, new| {
+            if old.ty != new.ty {
+                this.lines.push(format!(
+                    "! {} `{}`{} changes type from `{}` to `{}`",
+                    kind, new.name, scope, old.ty, new.ty
+                ));
+            }
+            if old.required != new.required {
+                this.lines.push(format!(
+                    "! {} `{}`{} becomes {}",
+                    kind,
+                    new.name,
+                    scope,
+                    if new.required { "required" } else { "optional" },
+                ));
+            }
+        });
+    }
+
+    fn compare_events(&mut self, scope: &str, old: &[EventConfig], new: &[EventConfig]) {
+        self.compare_items("event", scope, old, new, |_, _, _| {});
+    }
+
+    fn compare(&mut self, old: &BackendConfig, new: &BackendConfig) {
+        self.compare_items(
+            "element",
+            "",
+            &old.element,
+            &new.element,
+            |this, old, new| {
+                let scope = format!(" of element <{}>", new.tag_name);
+                this.compare_attributes("attribute", &scope, &old.attribute, &new.attribute);
+                this.compare_events(&scope, &old.event, &new.event);
+            },
+        );
+        self.compare_items(
+            "component",
+            "",
+            &old.component,
+            &new.component,
+            |this, old, new| {
+                let scope = format!(" of component <{}>", new.tag_name);
+                this.compare_attributes("property", &scope, &old.property, &new.property);
+                this.compare_events(&scope, &old.event, &new.event);
+            },
+        );
+        self.compare_attributes(
+            "global attribute",
+            "",
+            &old.global_attribute,
+            &new.global_attribute,
+        );
+        self.compare_items(
+            "global event",
+            "",
+            &old.global_event,
+            &new.global_event,
+            |_, _, _| {},
+        );
+    }
+}
+
+fn diff(old_path: &Path, new_path: &Path) -> anyhow::Result<i32> {
+    let (Some(old), Some(new)) = (parse_file(old_path)?, parse_file(new_path)?) else {
+        return Ok(1);
+    };
+    let old_version = &old.glass_easel_backend_config;
+    let new_version = &new.glass_easel_backend_config;
+    if (old_version.major_version, old_version.minor_version)
+        != (new_version.major_version, new_version.minor_version)
+    {
+        println!(
+            "! version {}.{} -> {}.{}",
+            old_version.major_version,
+            old_version.minor_version,
+            new_version.major_version,
+            new_version.minor_version,
+        );
+    }
+    let mut diff = ConfigDiff { lines: vec![] };
+    diff.compare(&old, &new);
+    for line in diff.lines.iter() {
+        println!("{}", line);
+    }
+    eprintln!("{} difference(s)", diff.lines.len());
+    Ok(0)
+}
+
+pub(super) async fn run(cmd: BackendConfigCommand) -> anyhow::Result<i32> {
+    match cmd {
+        BackendConfigCommand::Validate { file, overlays } => validate(&file, overlays),
+        BackendConfigCommand::Template { file } => template(&file),
+        BackendConfigCommand::Diff { old, new } => diff(&old, &new),
+    }
+}
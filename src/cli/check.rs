@@ -0,0 +1,225 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use super::{absolute_path, load_backend_config, report};
+use crate::{
+    config::{BackendConfigSource, ServerSettings, SettingsLayer},
+    context::{project::Project, FileLang},
+};
+
+/// The minimum severity which makes the check fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SeverityThreshold {
+    Error,
+    Warning,
+    Information,
+    Hint,
+    Never,
+}
+
+impl SeverityThreshold {
+    fn is_reached_by(self, severity: DiagnosticSeverity) -> bool {
+        let max = match self {
+            Self::Error => DiagnosticSeverity::ERROR,
+            Self::Warning => DiagnosticSeverity::WARNING,
+            Self::Information => DiagnosticSeverity::INFORMATION,
+            Self::Hint => DiagnosticSeverity::HINT,
+            Self::Never => return false,
+        };
+        severity <= max
+    }
+}
+
+/// The output format of the diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// `path:line:column: severity: message` lines
+    Human,
+    /// One JSON object per line for each diagnostic
+    Json,
+    /// A SARIF 2.1.0 log
+    Sarif,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CheckArgs {
+    /// The directory to search projects in
+    dir: PathBuf,
+    /// The glass-easel backend configuration file (TOML), overriding the one in `glass-easel-analyzer.toml`
+    #[arg(long)]
+    backend_config: Option<PathBuf>,
+    /// A backend configuration file applied on the base one (can be specified multiple times, applied in order)
+    #[arg(long)]
+    backend_config_overlay: Vec<PathBuf>,
+    /// Exit with a non-zero code if any diagnostic reaches this severity
+    #[arg(long, value_enum, default_value_t = SeverityThreshold::Error)]
+    fail_on: SeverityThreshold,
+    /// Skip a directory when searching projects (can be specified multiple times)
+    #[arg(long)]
+    ignore: Vec<PathBuf>,
+    /// Also check `.css` `.less` `.scss` files beside the WXML files
+    #[arg(long)]
+    enable_other_ss: bool,
+    /// The output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    format: OutputFormat,
+    /// Also output the UTF-8 byte offsets of the diagnostic ranges (JSON and SARIF only)
+    #[arg(long)]
+    byte_offsets: bool,
+}
+
+/// The diagnostics of a single file.
+pub(super) struct FileDiagnostics {
+    pub(super) abs_path: PathBuf,
+    pub(super) rel_path: String,
+    pub(super) diagnostics: Vec<CheckedDiagnostic>,
+}
+
+/// A diagnostic with the UTF-8 byte range in the file content.
+///
+/// The LSP range in `diag` is in UTF-16 line/column.
+pub(super) struct CheckedDiagnostic {
+    pub(super) diag: Diagnostic,
+    pub(super) byte_range: Range<usize>,
+}
+
+pub(super) fn severity_of(diag: &Diagnostic) -> DiagnosticSeverity {
+    diag.severity.unwrap_or(DiagnosticSeverity::ERROR)
+}
+
+fn collect_project_diagnostics(
+    project: &mut Project,
+    backend_config: &crate::BackendConfig,
+    base_dir: &Path,
+    ret: &mut Vec<FileDiagnostics>,
+) {
+    let mut files: Vec<_> = project
+        .list_file_paths()
+        .filter(|(_, lang)| {
+            matches!(
+                lang,
+                FileLang::Wxml | FileLang::Wxss | FileLang::Json | FileLang::OtherSs
+            )
+        })
+        .map(|(p, lang)| (p.to_path_buf(), lang))
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    for (abs_path, file_lang) in files {
+        let Some(content) = project.cached_file_content(&abs_path) else {
+            continue;
+        };
+        let content = content.content.clone();
+        let diag = match file_lang {
+            FileLang::Wxml => project.open_wxml(&abs_path, content),
+            FileLang::Wxss => project.open_wxss(&abs_path, content),
+            FileLang::Json => project.open_json(&abs_path, content),
+            FileLang::OtherSs => project.open_other_ss(&abs_path, content),
+            _ => continue,
+        };
+        let mut diagnostics = match diag {
+            Ok(x) => x,
+            Err(err) => {
+                eprintln!("error: failed to check {:?}: {}", abs_path, err);
+                continue;
+            }
+        };
+        diagnostics.append(&mut crate::checker::check_file(
+            project,
+            backend_config,
+            &abs_path,
+        ));
+        if diagnostics.is_empty() {
+            continue;
+        }
+        diagnostics.sort_by_key(|x| (x.range.start.line, x.range.start.character));
+        let Some(content) = project.cached_file_content(&abs_path) else {
+            continue;
+        };
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|diag| {
+                let start = content.content_index_for_line_utf16_col(
+                    diag.range.start.line,
+                    diag.range.start.character,
+                );
+                let end = content
+                    .content_index_for_line_utf16_col(diag.range.end.line, diag.range.end.character)
+                    .max(start);
+                CheckedDiagnostic {
+                    diag,
+                    byte_range: start..end,
+                }
+            })
+            .collect();
+        let rel_path = crate::utils::unix_rel_path(base_dir, &abs_path)
+            .unwrap_or_else(|_| abs_path.to_string_lossy().to_string());
+        ret.push(FileDiagnostics {
+            abs_path,
+            rel_path,
+            diagnostics,
+        });
+    }
+}
+
+pub(super) async fn run(args: CheckArgs) -> anyhow::Result<i32> {
+    let dir = absolute_path(&args.dir)?;
+    let cli_settings = SettingsLayer {
+        backend_config: args
+            .backend_config
+            .as_ref()
+            .map(|x| absolute_path(x).map(|x| Some(BackendConfigSource::Path(x))))
+            .transpose()?,
+        backend_config_overlays: Some(
+            args.backend_config_overlay
+                .iter()
+                .map(|x| absolute_path(x))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        ignore_paths: Some(
+            args.ignore
+                .iter()
+                .map(|x| absolute_path(x))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        enable_other_ss: args.enable_other_ss.then_some(true),
+        default_backend_config: None,
+    };
+    let settings = ServerSettings::resolve(std::slice::from_ref(&dir), &cli_settings);
+    let backend_config = load_backend_config(&settings.backend_config)?;
+    let options = settings.options;
+    let projects = Project::search_projects(&dir, &options).await;
+    if projects.is_empty() {
+        eprintln!("warning: no project found in {:?}", dir);
+    }
+    let mut files = vec![];
+    for mut project in projects {
+        project.init().await;
+        collect_project_diagnostics(&mut project, &backend_config, &dir, &mut files);
+    }
+    match args.format {
+        OutputFormat::Human => report::print_human(&files),
+        OutputFormat::Json => report::print_json_lines(&files, args.byte_offsets)?,
+        OutputFormat::Sarif => report::print_sarif(&files, &dir, args.byte_offsets)?,
+    }
+
+    let mut error_count = 0;
+    let mut warning_count = 0;
+    let mut failed = false;
+    for diag in files.iter().flat_map(|x| x.diagnostics.iter()) {
+        let severity = severity_of(&diag.diag);
+        match severity {
+            DiagnosticSeverity::ERROR => error_count += 1,
+            DiagnosticSeverity::WARNING => warning_count += 1,
+            _ => {}
+        }
+        if args.fail_on.is_reached_by(severity) {
+            failed = true;
+        }
+    }
+    eprintln!("{} error(s), {} warning(s)", error_count, warning_count);
+    Ok(if failed { 1 } else { 0 })
+}
@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+use glass_easel_template_compiler::parse::ParseErrorLevel;
+
+use super::absolute_path;
+use crate::{
+    config::{FormatConfig, ProjectConfig},
+    context::{project::FileContentMetadata, FileLang},
+    wxss::{stringify::stringify_style_sheet, StyleSheet},
+};
+
+/// Directories which are never searched for files to format.
+const SKIPPED_DIR_NAMES: [&str; 2] = ["node_modules", "miniprogram_npm"];
+
+#[derive(Debug, clap::Args)]
+pub struct FormatArgs {
+    /// The files or directories to format
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Do not write files, but exit with a non-zero code if any file would change
+    #[arg(long, conflicts_with = "write")]
+    check: bool,
+    /// Write the formatted content back to the files (otherwise print it)
+    #[arg(long)]
+    write: bool,
+    /// The number of spaces for each indentation level
+    #[arg(long)]
+    tab_size: Option<u32>,
+    /// Indent with tab characters instead of spaces
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    use_tabs: Option<bool>,
+    /// The preferred max line width
+    #[arg(long)]
+    line_width: Option<u32>,
+    /// Write hex colors in lowercase (WXSS only)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    lowercase_hex_colors: Option<bool>,
+    /// Sort the adjacent declarations by property names (WXSS only)
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    sort_properties: Option<bool>,
+    /// The configuration file (by default, `glass-easel-analyzer.toml` is searched upwards from each file)
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+impl FormatArgs {
+    fn format_config_overrides(&self) -> FormatConfig {
+        FormatConfig {
+            tab_size: self.tab_size,
+            use_tabs: self.use_tabs,
+            line_width: self.line_width,
+            single_quote: None,
+            lowercase_hex_colors: self.lowercase_hex_colors,
+            sort_properties: self.sort_properties,
+        }
+    }
+}
+
+fn is_supported_file(p: &Path) -> bool {
+    matches!(
+        p.extension().and_then(|x| x.to_str()),
+        Some("wxml" | "wxss")
+    )
+}
+
+fn collect_files(p: &Path, ret: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let Ok(dir) = std::fs::read_dir(p) else {
+        return Ok(());
+    };
+    let mut entries = dir.filter_map(|x| x.ok()).collect::<Vec<_>>();
+    entries.sort_by_key(|x| x.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_str().unwrap_or_default();
+            if name.starts_with('.') || SKIPPED_DIR_NAMES.contains(&name) {
+                continue;
+            }
+            collect_files(&path, ret)?;
+        } else if file_type.is_file() && is_supported_file(&path) {
+            ret.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn format_file(path: &Path, content: &str, config: &FormatConfig) -> anyhow::Result<String> {
+    if path.extension().and_then(|x| x.to_str()) == Some("wxss") {
+        let (sheet, warnings) = StyleSheet::parse_str(path, content);
+        if let Some(err) = warnings
+            .iter()
+            .find(|x| x.level() >= ParseErrorLevel::Error)
+        {
+            return Err(anyhow::anyhow!("{}", err));
+        }
+        return Ok(stringify_style_sheet(
+            &sheet,
+            &FileContentMetadata::new(content.to_string(), FileLang::Wxss),
+            &config.wxss_stringify_options(),
+        ));
+    }
+    let path_str = path.to_string_lossy();
+    let (template, state) = glass_easel_template_compiler::parse::parse(&path_str, content);
+    if let Some(err) = state
+        .warnings()
+        .find(|x| x.level() >= ParseErrorLevel::Error)
+    {
+        return Err(anyhow::anyhow!("{}", err));
+    }
+    crate::formatting::format_wxml(&template, config.stringify_options())
+}
+
+pub(super) async fn run(args: FormatArgs) -> anyhow::Result<i32> {
+    let mut files = vec![];
+    for p in args.paths.iter() {
+        let p = absolute_path(p)?;
+        if p.is_dir() {
+            collect_files(&p, &mut files)?;
+        } else if is_supported_file(&p) {
+            files.push(p);
+        } else {
+            eprintln!("warning: skipped {:?}: unsupported file type", p);
+        }
+    }
+    if !args.check && !args.write && files.len() > 1 {
+        return Err(anyhow::anyhow!(
+            "multiple files found; use `--write` or `--check` to format them"
+        ));
+    }
+
+    let fixed_config = match args.config.as_ref() {
+        None => None,
+        Some(p) => {
+            let s = std::fs::read_to_string(p)
+                .map_err(|err| anyhow::anyhow!("failed to read {:?}: {}", p, err))?;
+            let config = ProjectConfig::parse_str(&s)
+                .map_err(|err| anyhow::anyhow!("failed to parse {:?}: {}", p, err))?;
+            Some(config)
+        }
+    };
+    let overrides = args.format_config_overrides();
+
+    let mut changed_count = 0;
+    let mut failed_count = 0;
+    for path in files {
+        let mut config = match fixed_config.as_ref() {
+            Some(x) => x.format.clone(),
+            None => {
+                let dir = path.parent().unwrap_or(&path);
+                ProjectConfig::find(dir)?
+                    .map(|(_, x)| x.format)
+                    .unwrap_or_default()
+            }
+        };
+        config.merge(&overrides);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(x) => x,
+            Err(err) => {
+                eprintln!("error: failed to read {:?}: {}", path, err);
+                failed_count += 1;
+                continue;
+            }
+        };
+        let formatted = match format_file(&path, &content, &config) {
+            Ok(x) => x,
+            Err(err) => {
+                eprintln!("error: failed to format {:?}: {}", path, err);
+                failed_count += 1;
+                continue;
+            }
+        };
+        if !args.check && !args.write {
+            print!("{}", formatted);
+            continue;
+        }
+        if formatted == content {
+            continue;
+        }
+        changed_count += 1;
+        if args.check {
+            println!("{}", path.display());
+        } else if let Err(err) = std::fs::write(&path, formatted) {
+            eprintln!("error: failed to write {:?}: {}", path, err);
+            failed_count += 1;
+        }
+    }
+
+    if args.check {
+        eprintln!("{} file(s) would be reformatted", changed_count);
+    } else if args.write {
+        eprintln!("{} file(s) reformatted", changed_count);
+    }
+    if failed_count > 0 {
+        return Ok(2);
+    }
+    Ok(if args.check && changed_count > 0 {
+        1
+    } else {
+        0
+    })
+}
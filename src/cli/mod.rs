@@ -0,0 +1,70 @@
+//! Command-line subcommands which run without an LSP client.
+
+use std::path::{Path, PathBuf};
+
+use lsp_types::Diagnostic;
+
+use crate::{config::BackendConfigLayers, context::backend_configuration::BackendConfig};
+
+mod backend_config;
+mod check;
+mod format;
+mod report;
+
+pub use backend_config::BackendConfigCommand;
+pub use check::CheckArgs;
+pub use format::FormatArgs;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Check mini-program projects and print diagnostics
+    Check(CheckArgs),
+    /// Format WXML and WXSS files
+    Format(FormatArgs),
+    /// Validate, inspect or compare glass-easel backend configuration files
+    #[command(subcommand)]
+    BackendConfig(BackendConfigCommand),
+}
+
+/// Run a subcommand and return the process exit code.
+pub async fn run_command(cmd: Command) -> anyhow::Result<i32> {
+    match cmd {
+        Command::Check(args) => check::run(args).await,
+        Command::Format(args) => format::run(args).await,
+        Command::BackendConfig(cmd) => backend_config::run(cmd).await,
+    }
+}
+
+/// Format the diagnostics of backend configuration files as `path:line:column: severity: message` lines.
+fn backend_config_diagnostic_lines(diagnostics: &[(PathBuf, Vec<Diagnostic>)]) -> Vec<String> {
+    diagnostics
+        .iter()
+        .flat_map(|(p, list)| {
+            list.iter().map(move |diag| {
+                format!(
+                    "{}:{}:{}: {}: {}",
+                    p.display(),
+                    diag.range.start.line + 1,
+                    diag.range.start.character + 1,
+                    report::severity_name(check::severity_of(diag)),
+                    diag.message,
+                )
+            })
+        })
+        .collect()
+}
+
+fn load_backend_config(layers: &BackendConfigLayers) -> anyhow::Result<BackendConfig> {
+    if layers.base.is_none() {
+        eprintln!("warning: missing glass-easel backend configuration");
+    }
+    let (ret, diagnostics) = layers.load();
+    for line in backend_config_diagnostic_lines(&diagnostics) {
+        eprintln!("{}", line);
+    }
+    ret
+}
+
+fn absolute_path(p: &Path) -> anyhow::Result<PathBuf> {
+    Ok(std::path::absolute(p)?)
+}
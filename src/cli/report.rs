@@ -0,0 +1,146 @@
+//! Diagnostic printers for the `check` subcommand.
+
+use std::path::Path;
+
+use lsp_types::{DiagnosticSeverity, NumberOrString, Url};
+use serde_json::{json, Value};
+
+use super::check::{severity_of, CheckedDiagnostic, FileDiagnostics};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub(super) fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        DiagnosticSeverity::INFORMATION => "info",
+        _ => "hint",
+    }
+}
+
+fn sarif_level(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        _ => "note",
+    }
+}
+
+fn code_str(code: &NumberOrString) -> String {
+    match code {
+        NumberOrString::Number(x) => x.to_string(),
+        NumberOrString::String(x) => x.clone(),
+    }
+}
+
+pub(super) fn print_human(files: &[FileDiagnostics]) {
+    for file in files {
+        for CheckedDiagnostic { diag, .. } in file.diagnostics.iter() {
+            println!(
+                "{}:{}:{}: {}: {}",
+                file.rel_path,
+                diag.range.start.line + 1,
+                diag.range.start.character + 1,
+                severity_name(severity_of(diag)),
+                diag.message,
+            );
+        }
+    }
+}
+
+/// Print one JSON object per diagnostic.
+///
+/// Lines and columns are zero-based, and columns are counted in UTF-16 code units (same as LSP).
+pub(super) fn print_json_lines(
+    files: &[FileDiagnostics],
+    byte_offsets: bool,
+) -> anyhow::Result<()> {
+    for file in files {
+        for CheckedDiagnostic { diag, byte_range } in file.diagnostics.iter() {
+            let mut item = json!({
+                "file": file.rel_path,
+                "severity": severity_name(severity_of(diag)),
+                "message": diag.message,
+                "range": diag.range,
+            });
+            if let Some(code) = diag.code.as_ref() {
+                item["code"] = Value::String(code_str(code));
+            }
+            if byte_offsets {
+                item["byteRange"] = json!({
+                    "start": byte_range.start,
+                    "end": byte_range.end,
+                });
+            }
+            println!("{}", serde_json::to_string(&item)?);
+        }
+    }
+    Ok(())
+}
+
+/// Print a SARIF 2.1.0 log with a single run.
+///
+/// Lines and columns are one-based, and columns are counted in UTF-16 code units.
+pub(super) fn print_sarif(
+    files: &[FileDiagnostics],
+    base_dir: &Path,
+    byte_offsets: bool,
+) -> anyhow::Result<()> {
+    let base_uri = Url::from_directory_path(base_dir)
+        .map_err(|_| anyhow::anyhow!("illegal directory path {:?}", base_dir))?;
+    let mut results = vec![];
+    for file in files {
+        let uri = Url::from_file_path(&file.abs_path)
+            .ok()
+            .and_then(|x| base_uri.make_relative(&x))
+            .unwrap_or_else(|| file.rel_path.clone());
+        for CheckedDiagnostic { diag, byte_range } in file.diagnostics.iter() {
+            let mut region = json!({
+                "startLine": diag.range.start.line + 1,
+                "startColumn": diag.range.start.character + 1,
+                "endLine": diag.range.end.line + 1,
+                "endColumn": diag.range.end.character + 1,
+            });
+            if byte_offsets {
+                region["byteOffset"] = json!(byte_range.start);
+                region["byteLength"] = json!(byte_range.len());
+            }
+            let mut result = json!({
+                "level": sarif_level(severity_of(diag)),
+                "message": { "text": diag.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": uri,
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": region,
+                    },
+                }],
+            });
+            if let Some(code) = diag.code.as_ref() {
+                result["ruleId"] = Value::String(code_str(code));
+            }
+            results.push(result);
+        }
+    }
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            },
+            "originalUriBaseIds": {
+                "%SRCROOT%": { "uri": base_uri.as_str() },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+    println!("{}", serde_json::to_string_pretty(&log)?);
+    Ok(())
+}
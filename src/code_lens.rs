@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use lsp_types::{CodeLens, CodeLensParams, Command, Location};
+
+use crate::{
+    context::{project::Project, FileLang},
+    utils::location_to_lsp_range,
+    wxss::{rule::Selector, CSSParse, Rule},
+    wxss_utils::for_each_rule_in_style_sheet,
+    ServerContext,
+};
+
+/// The client-side command to show a reference list.
+///
+/// Arguments are the document uri, the position and the reference locations.
+const SHOW_REFERENCES_COMMAND: &str = "glass-easel-analyzer.showReferences";
+
+pub(crate) async fn code_lens(
+    ctx: ServerContext,
+    params: CodeLensParams,
+) -> anyhow::Result<Vec<CodeLens>> {
+    let uri = params.text_document.uri.clone();
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Vec<CodeLens>> {
+                let lens = match file_lang {
+                    FileLang::Wxml => collect_wxml_code_lens(project, &uri, &abs_path)?,
+                    FileLang::Wxss | FileLang::OtherSs => {
+                        collect_wxss_code_lens(project, &uri, &abs_path)?
+                    }
+                    _ => vec![],
+                };
+                Ok(lens)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+fn show_references_lens(
+    uri: &lsp_types::Url,
+    range: lsp_types::Range,
+    title: String,
+    locations: Vec<Location>,
+) -> anyhow::Result<CodeLens> {
+    Ok(CodeLens {
+        range,
+        command: Some(Command {
+            title,
+            command: SHOW_REFERENCES_COMMAND.to_string(),
+            arguments: Some(vec![
+                serde_json::to_value(uri)?,
+                serde_json::to_value(range.start)?,
+                serde_json::to_value(locations)?,
+            ]),
+        }),
+        data: None,
+    })
+}
+
+fn plural(n: usize, singular: &str, plural: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, singular)
+    } else {
+        format!("{} {}", n, plural)
+    }
+}
+
+fn collect_wxml_code_lens(
+    project: &Project,
+    uri: &lsp_types::Url,
+    abs_path: &Path,
+) -> anyhow::Result<Vec<CodeLens>> {
+    let mut ret = vec![];
+    if project.is_component(abs_path) {
+        let locations = crate::reference::component_usages(project, abs_path);
+        let mut users: Vec<_> = locations.iter().map(|x| &x.uri).collect();
+        users.sort();
+        users.dedup();
+        let title = format!(
+            "used by {}",
+            plural(users.len(), "page/component", "pages/components"),
+        );
+        ret.push(show_references_lens(
+            uri,
+            lsp_types::Range::default(),
+            title,
+            locations,
+        )?);
+    }
+    if let Ok(template) = project.get_wxml_tree(abs_path) {
+        for sub in template.globals.sub_templates.iter() {
+            let locations = crate::reference::template_usages(project, abs_path, &sub.name.name);
+            let title = plural(locations.len(), "reference", "references");
+            ret.push(show_references_lens(
+                uri,
+                location_to_lsp_range(&sub.name_location),
+                title,
+                locations,
+            )?);
+        }
+    }
+    Ok(ret)
+}
+
+fn collect_wxss_code_lens(
+    project: &Project,
+    uri: &lsp_types::Url,
+    abs_path: &Path,
+) -> anyhow::Result<Vec<CodeLens>> {
+    let Ok(sheet) = project.get_style_sheet(abs_path, false) else {
+        return Ok(vec![]);
+    };
+    if project
+        .get_wxml_tree(&sheet.path.with_extension("wxml"))
+        .is_err()
+    {
+        return Ok(vec![]);
+    }
+    let mut lens_list = vec![];
+    for_each_rule_in_style_sheet(sheet, |rule| {
+        let Rule::Style(style_rule) = rule else {
+            return;
+        };
+        let mut class_names = vec![];
+        for list in style_rule.selector.iter_values() {
+            for sel in list.iter() {
+                if let Selector::Class(_, name) = sel {
+                    if !class_names.contains(&name.content.as_str()) {
+                        class_names.push(name.content.as_str());
+                    }
+                }
+            }
+        }
+        if class_names.is_empty() {
+            return;
+        }
+        let mut locations = vec![];
+        for name in class_names {
+            for loc in crate::reference::class_usages(project, &sheet.path, name) {
+                if !locations.contains(&loc) {
+                    locations.push(loc);
+                }
+            }
+        }
+        let title = format!("used by {}", plural(locations.len(), "element", "elements"));
+        lens_list.push((
+            location_to_lsp_range(&style_rule.selector.location()),
+            title,
+            locations,
+        ));
+    });
+    lens_list
+        .into_iter()
+        .map(|(range, title, locations)| show_references_lens(uri, range, title, locations))
+        .collect()
+}
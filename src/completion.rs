@@ -8,15 +8,17 @@ use glass_easel_template_compiler::parse::{
 };
 use itertools::Itertools;
 use lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionList, CompletionParams, InsertTextFormat,
+    CompletionItem, CompletionItemKind, CompletionList, CompletionParams, Documentation,
+    InsertTextFormat, MarkupContent, MarkupKind, Url,
 };
 
 use crate::{
     context::{
-        backend_configuration::MediaFeatureType,
+        backend_configuration::{MediaFeatureType, PropertyType},
         project::{FileContentMetadata, Project},
         FileLang,
     },
+    hover::{attribute_markdown, reference_args_str},
     wxml_utils::{
         for_each_static_class_name_in_element, for_each_template_element, Token as WxmlToken,
         TokenStaticStyleValuePart,
@@ -136,6 +138,185 @@ pub(crate) async fn completion(
     Ok(ret)
 }
 
+/// The data attached to the completion items, used to find the documentation in `completionItem/resolve` .
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum CompletionData {
+    Component {
+        tag_name: String,
+    },
+    Element {
+        tag_name: String,
+    },
+    Property {
+        tag_name: String,
+        name: String,
+    },
+    Attribute {
+        tag_name: String,
+        name: String,
+    },
+    Event {
+        tag_name: Option<String>,
+        name: String,
+    },
+    ValueOption {
+        tag_name: String,
+        attr_name: String,
+        value: String,
+    },
+    StyleProperty {
+        name: String,
+    },
+    PseudoClass {
+        name: String,
+    },
+    PseudoElement {
+        name: String,
+    },
+    MediaFeature {
+        name: String,
+    },
+}
+
+pub(crate) async fn completion_resolve(
+    ctx: ServerContext,
+    mut item: CompletionItem,
+) -> anyhow::Result<CompletionItem> {
+    let Some(data) = item
+        .data
+        .clone()
+        .and_then(|x| serde_json::from_value::<CompletionData>(x).ok())
+    else {
+        return Ok(item);
+    };
+    let backend_config = ctx.backend_config();
+    if let Some((detail, doc)) = completion_documentation(&backend_config, &data) {
+        item.detail = Some(detail.to_string());
+        if !doc.is_empty() {
+            item.documentation = Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc,
+            }));
+        }
+    }
+    Ok(item)
+}
+
+/// Find the kind and the markdown documentation of the completion item.
+fn completion_documentation(
+    backend_config: &BackendConfig,
+    data: &CompletionData,
+) -> Option<(&'static str, String)> {
+    let deprecated_args = |deprecated: bool| {
+        if deprecated {
+            "*Deprecated.*\n\n"
+        } else {
+            ""
+        }
+    };
+    let simple = |description: &str, reference: &Option<Url>, deprecated: bool| {
+        let doc = format!(
+            "{}{}{}",
+            deprecated_args(deprecated),
+            description,
+            reference_args_str(reference)
+        );
+        // the reference is prefixed with blank lines even without a description
+        doc.trim_start().to_string()
+    };
+    let ret = match data {
+        CompletionData::Component { tag_name } => {
+            let x = backend_config.search_component(tag_name)?;
+            (
+                "component",
+                simple(&x.description, &x.reference, x.deprecated),
+            )
+        }
+        CompletionData::Element { tag_name } => {
+            let x = backend_config.search_element(tag_name)?;
+            (
+                "element",
+                simple(&x.description, &x.reference, x.deprecated),
+            )
+        }
+        CompletionData::Property { tag_name, name } => {
+            let x = backend_config.search_property(tag_name, name)?;
+            let doc = attribute_markdown(x, "property");
+            (
+                "property",
+                format!("{}{}", deprecated_args(x.deprecated), doc),
+            )
+        }
+        CompletionData::Attribute { tag_name, name } => {
+            let x = backend_config.search_attribute(tag_name, name)?;
+            let doc = attribute_markdown(x, "attribute");
+            (
+                "attribute",
+                format!("{}{}", deprecated_args(x.deprecated), doc),
+            )
+        }
+        CompletionData::Event { tag_name, name } => {
+            let x = match tag_name {
+                Some(tag_name) => backend_config.search_event(tag_name, name),
+                None => backend_config.search_global_event(name),
+            }?;
+            ("event", simple(&x.description, &x.reference, x.deprecated))
+        }
+        CompletionData::ValueOption {
+            tag_name,
+            attr_name,
+            value,
+        } => {
+            let attr = backend_config
+                .search_property(tag_name, attr_name)
+                .or_else(|| backend_config.search_attribute(tag_name, attr_name))?;
+            let x = attr.value_option.iter().find(|x| x.value == *value)?;
+            (
+                "value",
+                simple(&x.description, &attr.reference, x.deprecated),
+            )
+        }
+        CompletionData::StyleProperty { name } => {
+            let x = backend_config
+                .style_property
+                .iter()
+                .find(|x| x.name == *name)?;
+            ("property", simple(&x.description, &x.reference, false))
+        }
+        CompletionData::PseudoClass { name } => {
+            let x = backend_config
+                .pseudo_class
+                .iter()
+                .find(|x| x.name == *name)?;
+            ("pseudo class", simple(&x.description, &x.reference, false))
+        }
+        CompletionData::PseudoElement { name } => {
+            let x = backend_config
+                .pseudo_element
+                .iter()
+                .find(|x| x.name == *name)?;
+            (
+                "pseudo element",
+                simple(&x.description, &x.reference, false),
+            )
+        }
+        CompletionData::MediaFeature { name } => {
+            let x = backend_config
+                .media_feature
+                .iter()
+                .find(|x| x.name == *name)?;
+            ("media feature", simple(&x.description, &x.reference, false))
+        }
+    };
+    Some(ret)
+}
+
+fn with_data(mut item: CompletionItem, data: CompletionData) -> CompletionItem {
+    item.data = serde_json::to_value(data).ok();
+    item
+}
+
 fn simple_completion_item(
     s: impl Into<String>,
     kind: CompletionItemKind,
@@ -212,6 +393,7 @@ fn completion_wxml(
             change_attributes,
             class,
             style,
+            generics,
             common,
             ..
         } = &elem.kind
@@ -224,7 +406,44 @@ fn completion_wxml(
                     .find(|x| x.name.as_str() == name)
                     .is_some()
             };
-            if let Some(_target_path) = project.get_target_component_path(abs_path, &tag_name.name)
+            if let Some(script) = project.get_target_component_script(abs_path, &tag_name.name) {
+                let prop_names: Vec<_> = script
+                    .properties
+                    .iter()
+                    .map(|(_, prop)| (crate::utils::camel_to_dash(&prop.name), *prop))
+                    .filter(|(name, _)| !has_attr(name))
+                    .collect();
+                for (name, prop) in prop_names.iter() {
+                    if prop.ty.as_deref() == Some("boolean") {
+                        items.push(simple_completion_item(
+                            name,
+                            CompletionItemKind::VARIABLE,
+                            false,
+                        ));
+                    } else {
+                        items.push(snippet_completion_item(
+                            name,
+                            format!("{}=\"$0\"", name),
+                            CompletionItemKind::VARIABLE,
+                            false,
+                        ));
+                    }
+                }
+                if !has_prefix {
+                    for name in ["model:", "change:"] {
+                        let choices = prop_names.iter().map(|(name, _)| name).join(",");
+                        if !choices.is_empty() {
+                            items.push(snippet_completion_item(
+                                name,
+                                format!("{}${{1|{}|}}=\"{{{{ $0 }}}}\"", name, choices),
+                                CompletionItemKind::KEYWORD,
+                                false,
+                            ));
+                        }
+                    }
+                }
+            } else if let Some(_target_path) =
+                project.get_target_component_path(abs_path, &tag_name.name)
             {
                 // empty
             } else if let Some(props) = backend_config.list_properties(&tag_name.name) {
@@ -233,20 +452,23 @@ fn completion_wxml(
                     if has_attr(name) {
                         continue;
                     }
-                    if prop.ty == "boolean" {
-                        items.push(simple_completion_item(
-                            name,
-                            CompletionItemKind::VARIABLE,
-                            prop.deprecated,
-                        ));
+                    let item = if prop.ty == PropertyType::Boolean {
+                        simple_completion_item(name, CompletionItemKind::VARIABLE, prop.deprecated)
                     } else {
-                        items.push(snippet_completion_item(
+                        snippet_completion_item(
                             name,
                             format!("{}=\"$0\"", name),
                             CompletionItemKind::VARIABLE,
                             prop.deprecated,
-                        ));
-                    }
+                        )
+                    };
+                    items.push(with_data(
+                        item,
+                        CompletionData::Property {
+                            tag_name: tag_name.name.to_string(),
+                            name: name.clone(),
+                        },
+                    ));
                 }
                 if !has_prefix {
                     for name in ["model:", "change:"] {
@@ -272,11 +494,17 @@ fn completion_wxml(
                     if has_attr(&name) {
                         continue;
                     }
-                    items.push(snippet_completion_item(
-                        name,
-                        format!("{}=\"$0\"", name),
-                        CompletionItemKind::VARIABLE,
-                        attr.deprecated,
+                    items.push(with_data(
+                        snippet_completion_item(
+                            name,
+                            format!("{}=\"$0\"", name),
+                            CompletionItemKind::VARIABLE,
+                            attr.deprecated,
+                        ),
+                        CompletionData::Attribute {
+                            tag_name: tag_name.name.to_string(),
+                            name: name.clone(),
+                        },
                     ));
                 }
                 if !has_prefix {
@@ -370,13 +598,39 @@ fn completion_wxml(
                     false,
                 ));
             }
-            for name in ["generic:"] {
-                items.push(snippet_completion_item(
-                    name,
-                    format!("{}$1=\"$0\"", name),
-                    CompletionItemKind::KEYWORD,
-                    false,
-                ));
+            if let Some(target_generics) =
+                project.get_target_component_generics(abs_path, &tag_name.name)
+            {
+                let choices = project
+                    .iter_using_components_keys(abs_path)
+                    .filter(|x| Ident::is_valid(x))
+                    .join(",");
+                for generic_name in target_generics.keys().sorted() {
+                    if generics.iter().any(|x| x.name.name == generic_name) {
+                        continue;
+                    }
+                    let name = format!("generic:{}", generic_name);
+                    let snippet = if !choices.is_empty() {
+                        format!("{}=\"${{1|{}|}}\"$0", name, choices)
+                    } else {
+                        format!("{}=\"$0\"", name)
+                    };
+                    items.push(snippet_completion_item(
+                        name,
+                        snippet,
+                        CompletionItemKind::KEYWORD,
+                        false,
+                    ));
+                }
+            } else {
+                for name in ["generic:"] {
+                    items.push(snippet_completion_item(
+                        name,
+                        format!("{}$1=\"$0\"", name),
+                        CompletionItemKind::KEYWORD,
+                        false,
+                    ));
+                }
             }
             for name in [
                 "wx:if",
@@ -407,6 +661,18 @@ fn completion_wxml(
                         .map(|x| &x.name)
                         .filter(|x| !has_event(common, x))
                         .join(",")
+                } else if let Some(script) =
+                    project.get_target_component_script(abs_path, &tag_name.name)
+                {
+                    let choices = backend_config.list_global_events();
+                    script
+                        .events
+                        .iter()
+                        .map(|(_, x)| &x.name)
+                        .chain(choices.map(|x| &x.name))
+                        .filter(|x| !has_event(common, x))
+                        .unique()
+                        .join(",")
                 } else {
                     let choices = backend_config.list_global_events();
                     choices
@@ -566,20 +832,30 @@ fn completion_wxml(
                 }
                 for comp in backend_config.component.iter() {
                     let name = &comp.tag_name;
-                    items.push(snippet_completion_item(
-                        name,
-                        format!("{key}>$0</{key}>", key = name),
-                        CompletionItemKind::CLASS,
-                        comp.deprecated,
+                    items.push(with_data(
+                        snippet_completion_item(
+                            name,
+                            format!("{key}>$0</{key}>", key = name),
+                            CompletionItemKind::CLASS,
+                            comp.deprecated,
+                        ),
+                        CompletionData::Component {
+                            tag_name: name.clone(),
+                        },
                     ));
                 }
                 for elem in backend_config.element.iter() {
                     let name = &elem.tag_name;
-                    items.push(snippet_completion_item(
-                        name,
-                        format!("{key}>$0</{key}>", key = name),
-                        CompletionItemKind::CLASS,
-                        elem.deprecated,
+                    items.push(with_data(
+                        snippet_completion_item(
+                            name,
+                            format!("{key}>$0</{key}>", key = name),
+                            CompletionItemKind::CLASS,
+                            elem.deprecated,
+                        ),
+                        CompletionData::Element {
+                            tag_name: name.clone(),
+                        },
                     ));
                 }
                 for key in ["slot", "block"] {
@@ -653,17 +929,27 @@ fn completion_wxml(
                 }
             }
             for comp in backend_config.component.iter() {
-                items.push(simple_completion_item(
-                    &comp.tag_name,
-                    CompletionItemKind::CLASS,
-                    comp.deprecated,
+                items.push(with_data(
+                    simple_completion_item(
+                        &comp.tag_name,
+                        CompletionItemKind::CLASS,
+                        comp.deprecated,
+                    ),
+                    CompletionData::Component {
+                        tag_name: comp.tag_name.clone(),
+                    },
                 ));
             }
             for elem in backend_config.element.iter() {
-                items.push(simple_completion_item(
-                    &elem.tag_name,
-                    CompletionItemKind::CLASS,
-                    elem.deprecated,
+                items.push(with_data(
+                    simple_completion_item(
+                        &elem.tag_name,
+                        CompletionItemKind::CLASS,
+                        elem.deprecated,
+                    ),
+                    CompletionData::Element {
+                        tag_name: elem.tag_name.clone(),
+                    },
                 ));
             }
             Some(CompletionList {
@@ -676,6 +962,87 @@ fn completion_wxml(
         WxmlToken::ModelAttributeName(_attr_name, elem) => handle_attr(elem, true),
         WxmlToken::ChangeAttributeName(_attr_name, elem) => handle_attr(elem, true),
         WxmlToken::AttributeKeyword(_loc, elem) => handle_attr(elem, false),
+        WxmlToken::GenericName(cur_name, elem) => {
+            let mut items: Vec<CompletionItem> = vec![];
+            if let ElementKind::Normal {
+                tag_name, generics, ..
+            } = &elem.kind
+            {
+                if let Some(target_generics) =
+                    project.get_target_component_generics(abs_path, &tag_name.name)
+                {
+                    for name in target_generics.keys().sorted() {
+                        if generics
+                            .iter()
+                            .any(|x| x.name.name == name && !std::ptr::eq(&x.name, cur_name))
+                        {
+                            continue;
+                        }
+                        items.push(simple_completion_item(
+                            name,
+                            CompletionItemKind::VARIABLE,
+                            false,
+                        ));
+                    }
+                }
+            }
+            Some(CompletionList {
+                is_incomplete: false,
+                items,
+            })
+        }
+        WxmlToken::GenericRef(_value, _tag_name) => {
+            let mut items: Vec<CompletionItem> = vec![];
+            for key in project.iter_using_components_keys(abs_path) {
+                if Ident::is_valid(key) {
+                    items.push(simple_completion_item(
+                        key,
+                        CompletionItemKind::CLASS,
+                        false,
+                    ));
+                }
+            }
+            Some(CompletionList {
+                is_incomplete: false,
+                items,
+            })
+        }
+        WxmlToken::StaticEventHandler(..) | WxmlToken::EventHandler(..) => {
+            let mut items: Vec<CompletionItem> = vec![];
+            if let Some(script) = project.get_own_component_script(abs_path) {
+                for (_, method) in script.methods.iter() {
+                    items.push(simple_completion_item(
+                        &method.name,
+                        CompletionItemKind::METHOD,
+                        false,
+                    ));
+                }
+            }
+            Some(CompletionList {
+                is_incomplete: false,
+                items,
+            })
+        }
+        WxmlToken::SlotName(_loc, _name, parent) => {
+            let mut items: Vec<CompletionItem> = vec![];
+            if let ElementKind::Normal { tag_name, .. } = &parent.kind {
+                if let Some(slot_names) =
+                    project.get_target_component_slot_names(abs_path, &tag_name.name)
+                {
+                    for name in slot_names {
+                        items.push(simple_completion_item(
+                            name,
+                            CompletionItemKind::VALUE,
+                            false,
+                        ));
+                    }
+                }
+            }
+            Some(CompletionList {
+                is_incomplete: false,
+                items,
+            })
+        }
         WxmlToken::StaticClassName(_loc, _name, elem) => match &elem.kind {
             ElementKind::Normal { class, .. } => {
                 let items = collect_classes_in_wxss(project, abs_path)
@@ -707,7 +1074,12 @@ fn completion_wxml(
                         }
                         _ => true,
                     })
-                    .map(|x| simple_completion_item(x, CompletionItemKind::PROPERTY, false))
+                    .map(|x| {
+                        with_data(
+                            simple_completion_item(x, CompletionItemKind::PROPERTY, false),
+                            CompletionData::StyleProperty { name: x.clone() },
+                        )
+                    })
                     .collect();
                 Some(CompletionList {
                     is_incomplete: false,
@@ -753,7 +1125,7 @@ fn completion_wxml(
                         })
                         .map(|config| {
                             let name = &config.name;
-                            if config.options.len() > 0 {
+                            let item = if !config.options.is_empty() {
                                 let options_str = config.options.join(",");
                                 snippet_completion_item(
                                     name,
@@ -768,7 +1140,8 @@ fn completion_wxml(
                                     CompletionItemKind::PROPERTY,
                                     false,
                                 )
-                            }
+                            };
+                            with_data(item, CompletionData::StyleProperty { name: name.clone() })
                         })
                         .collect();
                     Some(CompletionList {
@@ -823,15 +1196,35 @@ fn completion_wxml(
                     ElementKind::Normal { tag_name, .. } => Some(tag_name),
                     _ => None,
                 };
-                if let Some(events) = tag_name.and_then(|x| backend_config.list_events(&x.name)) {
-                    for ev in events {
+                let script =
+                    tag_name.and_then(|x| project.get_target_component_script(abs_path, &x.name));
+                if let Some(script) = script {
+                    for (_, ev) in script.events.iter() {
                         if has_event(common, &ev.name) {
                             continue;
                         }
                         items.push(simple_completion_item(
                             &ev.name,
-                            CompletionItemKind::VARIABLE,
-                            ev.deprecated,
+                            CompletionItemKind::EVENT,
+                            false,
+                        ));
+                    }
+                }
+                if let Some(events) = tag_name.and_then(|x| backend_config.list_events(&x.name)) {
+                    for ev in events {
+                        if has_event(common, &ev.name) {
+                            continue;
+                        }
+                        items.push(with_data(
+                            simple_completion_item(
+                                &ev.name,
+                                CompletionItemKind::VARIABLE,
+                                ev.deprecated,
+                            ),
+                            CompletionData::Event {
+                                tag_name: tag_name.map(|x| x.name.to_string()),
+                                name: ev.name.clone(),
+                            },
                         ));
                     }
                 } else {
@@ -840,10 +1233,16 @@ fn completion_wxml(
                         if has_event(common, &ev.name) {
                             continue;
                         }
-                        items.push(simple_completion_item(
-                            &ev.name,
-                            CompletionItemKind::VARIABLE,
-                            ev.deprecated,
+                        items.push(with_data(
+                            simple_completion_item(
+                                &ev.name,
+                                CompletionItemKind::VARIABLE,
+                                ev.deprecated,
+                            ),
+                            CompletionData::Event {
+                                tag_name: None,
+                                name: ev.name.clone(),
+                            },
                         ));
                     }
                 };
@@ -855,23 +1254,38 @@ fn completion_wxml(
         }
         WxmlToken::AttributeStaticValue(_loc, _value, name, elem) => {
             if let ElementKind::Normal { tag_name, .. } = &elem.kind {
-                let value_options1 = backend_config
+                let attr = backend_config
                     .search_property(&tag_name.name, &name.name)
-                    .map(|x| &x.value_option);
-                let value_options2 = backend_config
-                    .search_attribute(&tag_name.name, &name.name)
-                    .map(|x| &x.value_option);
-                if let Some(value_options) = value_options1.or(value_options2) {
-                    let list = value_options
+                    .or_else(|| backend_config.search_attribute(&tag_name.name, &name.name));
+                if let Some(attr) = attr {
+                    let mut list: Vec<_> = attr
+                        .value_option
                         .iter()
                         .map(|x| {
-                            simple_completion_item(
-                                &x.value,
-                                CompletionItemKind::ENUM_MEMBER,
-                                x.deprecated,
+                            with_data(
+                                simple_completion_item(
+                                    &x.value,
+                                    CompletionItemKind::ENUM_MEMBER,
+                                    x.deprecated,
+                                ),
+                                CompletionData::ValueOption {
+                                    tag_name: tag_name.name.to_string(),
+                                    attr_name: name.name.to_string(),
+                                    value: x.value.clone(),
+                                },
                             )
                         })
                         .collect();
+                    if list.is_empty() {
+                        // fallback to the string literals in the type
+                        for value in attr.ty.enum_values().unwrap_or_default() {
+                            list.push(simple_completion_item(
+                                value,
+                                CompletionItemKind::ENUM_MEMBER,
+                                false,
+                            ));
+                        }
+                    }
                     Some(CompletionList {
                         is_incomplete: false,
                         items: list,
@@ -928,22 +1342,28 @@ fn completion_wxss(
             let mut items: Vec<CompletionItem> = vec![];
             for config in backend_config.style_property.iter() {
                 let name = config.name.as_str();
-                if config.options.len() > 0 {
+                let item = if !config.options.is_empty() {
                     let options_str = config.options.join(",");
-                    items.push(snippet_completion_item(
+                    snippet_completion_item(
                         name,
                         format!("{}: ${{1|{}|}};", name, options_str),
                         CompletionItemKind::PROPERTY,
                         false,
-                    ));
+                    )
                 } else {
-                    items.push(snippet_completion_item(
+                    snippet_completion_item(
                         name,
                         format!("{}: $0;", name),
                         CompletionItemKind::PROPERTY,
                         false,
-                    ));
-                }
+                    )
+                };
+                items.push(with_data(
+                    item,
+                    CompletionData::StyleProperty {
+                        name: config.name.clone(),
+                    },
+                ));
             }
             Some(CompletionList {
                 is_incomplete: false,
@@ -980,10 +1400,11 @@ fn completion_wxss(
             let mut items: Vec<CompletionItem> = vec![];
             for config in backend_config.style_property.iter() {
                 let name = config.name.as_str();
-                items.push(simple_completion_item(
-                    name,
-                    CompletionItemKind::PROPERTY,
-                    false,
+                items.push(with_data(
+                    simple_completion_item(name, CompletionItemKind::PROPERTY, false),
+                    CompletionData::StyleProperty {
+                        name: config.name.clone(),
+                    },
                 ));
             }
             Some(CompletionList {
@@ -995,28 +1416,30 @@ fn completion_wxss(
             let mut items: Vec<CompletionItem> = vec![];
             for config in backend_config.media_feature.iter() {
                 let mut handle_item = |name: &str, has_value: bool| {
-                    if config.options.len() > 0 {
+                    let item = if !config.options.is_empty() {
                         let options_str = config.options.join(",");
-                        items.push(snippet_completion_item(
+                        snippet_completion_item(
                             name,
                             format!("{}: ${{1|{}|}}", name, options_str),
                             CompletionItemKind::PROPERTY,
                             false,
-                        ));
+                        )
                     } else if has_value {
-                        items.push(snippet_completion_item(
+                        snippet_completion_item(
                             name,
                             format!("{}: $0", name),
                             CompletionItemKind::PROPERTY,
                             false,
-                        ));
+                        )
                     } else {
-                        items.push(simple_completion_item(
-                            name,
-                            CompletionItemKind::PROPERTY,
-                            false,
-                        ));
-                    }
+                        simple_completion_item(name, CompletionItemKind::PROPERTY, false)
+                    };
+                    items.push(with_data(
+                        item,
+                        CompletionData::MediaFeature {
+                            name: config.name.clone(),
+                        },
+                    ));
                 };
                 let name = config.name.as_str();
                 if config.ty == MediaFeatureType::Range {
@@ -1066,7 +1489,14 @@ fn completion_wxss(
             let item_set = backend_config
                 .pseudo_class
                 .iter()
-                .map(|x| simple_completion_item(&x.name, CompletionItemKind::OPERATOR, false))
+                .map(|x| {
+                    with_data(
+                        simple_completion_item(&x.name, CompletionItemKind::OPERATOR, false),
+                        CompletionData::PseudoClass {
+                            name: x.name.clone(),
+                        },
+                    )
+                })
                 .collect();
             Some(CompletionList {
                 is_incomplete: false,
@@ -1077,7 +1507,14 @@ fn completion_wxss(
             let item_set = backend_config
                 .pseudo_element
                 .iter()
-                .map(|x| simple_completion_item(&x.name, CompletionItemKind::OPERATOR, false))
+                .map(|x| {
+                    with_data(
+                        simple_completion_item(&x.name, CompletionItemKind::OPERATOR, false),
+                        CompletionData::PseudoElement {
+                            name: x.name.clone(),
+                        },
+                    )
+                })
                 .collect();
             Some(CompletionList {
                 is_incomplete: false,
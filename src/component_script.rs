@@ -0,0 +1,599 @@
+use std::{collections::HashMap, path::Path};
+
+use swc_common::{BytePos, Spanned};
+use swc_ecma_ast::{
+    Callee, Decl, Expr, Lit, ModuleDecl, ModuleItem, ObjectLit, Pat, Program, Prop, PropName,
+    PropOrSpread, Stmt,
+};
+use swc_ecma_lexer::{
+    token::{IdentLike, Token, Word},
+    Lexer, Parser, StringInput, Syntax,
+};
+
+use crate::{
+    context::project::FileContentMetadata,
+    wxss::{Location, Position},
+};
+
+/// A property declared in the `properties` field of a component or a behavior.
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyDefinition {
+    pub(crate) name: String,
+    pub(crate) location: Location,
+    pub(crate) ty: Option<String>,
+    pub(crate) default_value: Option<String>,
+}
+
+/// A method declared in the `methods` field of a component or a behavior,
+/// or a top-level function of a page.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodDefinition {
+    pub(crate) name: String,
+    pub(crate) location: Location,
+}
+
+/// An event triggered by a `triggerEvent` call with a static event name.
+#[derive(Debug, Clone)]
+pub(crate) struct EventDefinition {
+    pub(crate) name: String,
+    pub(crate) location: Location,
+}
+
+/// The static information extracted from the script file of a component or a behavior.
+#[derive(Debug, Default)]
+pub(crate) struct ComponentScript {
+    /// The `multipleSlots` option if it is statically written.
+    pub(crate) multiple_slots: Option<bool>,
+    pub(crate) properties: Vec<PropertyDefinition>,
+    pub(crate) methods: Vec<MethodDefinition>,
+    pub(crate) events: Vec<EventDefinition>,
+    pub(crate) external_classes: Vec<String>,
+    /// The module specifiers of the behaviors imported from other files.
+    pub(crate) behaviors: Vec<String>,
+    /// Whether all properties can be statically collected.
+    ///
+    /// It is `false` when the file cannot be parsed or some definitions are generated dynamically.
+    pub(crate) is_complete: bool,
+}
+
+/// The script information of a component merged with the behaviors it uses.
+#[derive(Debug, Default)]
+pub(crate) struct ComponentScriptInfo<'a> {
+    pub(crate) properties: Vec<(&'a Path, &'a PropertyDefinition)>,
+    pub(crate) methods: Vec<(&'a Path, &'a MethodDefinition)>,
+    pub(crate) events: Vec<(&'a Path, &'a EventDefinition)>,
+    pub(crate) external_classes: Vec<&'a str>,
+    /// Whether all scripts are found and statically analyzable.
+    pub(crate) is_complete: bool,
+}
+
+impl<'a> ComponentScriptInfo<'a> {
+    /// Find the property for an attribute name, which may be written in dash-case.
+    pub(crate) fn find_property(
+        &self,
+        attr_name: &str,
+    ) -> Option<(&'a Path, &'a PropertyDefinition)> {
+        let camel_name = crate::utils::dash_to_camel(attr_name);
+        self.properties
+            .iter()
+            .find(|(_, x)| x.name == attr_name || x.name == camel_name)
+            .cloned()
+    }
+
+    pub(crate) fn find_method(
+        &self,
+        method_name: &str,
+    ) -> Option<(&'a Path, &'a MethodDefinition)> {
+        self.methods
+            .iter()
+            .find(|(_, x)| x.name == method_name)
+            .cloned()
+    }
+
+    pub(crate) fn find_event(&self, event_name: &str) -> Option<(&'a Path, &'a EventDefinition)> {
+        self.events
+            .iter()
+            .find(|(_, x)| x.name == event_name)
+            .cloned()
+    }
+}
+
+struct ScriptParser<'a> {
+    src: &'a str,
+    content: &'a FileContentMetadata,
+    ret: ComponentScript,
+}
+
+impl<'a> ScriptParser<'a> {
+    fn location(&self, span: swc_common::Span) -> Location {
+        let pos = |index: BytePos| {
+            let (line, utf16_col) = self
+                .content
+                .line_utf16_col_for_content_index(index.0 as usize);
+            Position { line, utf16_col }
+        };
+        pos(span.lo)..pos(span.hi)
+    }
+
+    fn source_text(&self, span: swc_common::Span) -> &'a str {
+        &self.src[span.lo.0 as usize..span.hi.0 as usize]
+    }
+
+    fn handle_definition(
+        &mut self,
+        ctor: &str,
+        def: &ObjectLit,
+        imported: &HashMap<String, String>,
+        local_behaviors: &[String],
+    ) {
+        if ctor == "Page" {
+            self.handle_methods(def, true);
+        }
+        for prop in def.props.iter() {
+            let PropOrSpread::Prop(prop) = prop else {
+                self.ret.is_complete = false;
+                continue;
+            };
+            let (name, value) = match &**prop {
+                Prop::KeyValue(x) => (prop_name_str(&x.key), &*x.value),
+                _ => continue,
+            };
+            match (ctor, name) {
+                ("Component", Some("options")) => {
+                    if let Some(options) = as_object(value) {
+                        if let Some(x) = find_prop(options, "multipleSlots").and_then(as_bool) {
+                            self.ret.multiple_slots = Some(x);
+                        }
+                    }
+                }
+                (_, Some("properties")) => match as_object(value) {
+                    Some(obj) => self.handle_properties(obj),
+                    None => self.ret.is_complete = false,
+                },
+                (_, Some("methods")) => match as_object(value) {
+                    Some(obj) => self.handle_methods(obj, false),
+                    None => self.ret.is_complete = false,
+                },
+                (_, Some("externalClasses")) => match as_array(value) {
+                    Some(items) => {
+                        for item in items {
+                            match item.and_then(as_str) {
+                                Some(x) => self.ret.external_classes.push(x.to_string()),
+                                None => self.ret.is_complete = false,
+                            }
+                        }
+                    }
+                    None => self.ret.is_complete = false,
+                },
+                (_, Some("behaviors")) => match as_array(value) {
+                    Some(items) => {
+                        for item in items {
+                            match item.and_then(|x| behavior_source(x, imported)) {
+                                Some(BehaviorSource::Local(x)) if local_behaviors.contains(&x) => {}
+                                Some(BehaviorSource::Module(x)) => self.ret.behaviors.push(x),
+                                _ => self.ret.is_complete = false,
+                            }
+                        }
+                    }
+                    None => self.ret.is_complete = false,
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_properties(&mut self, obj: &ObjectLit) {
+        for prop in obj.props.iter() {
+            let PropOrSpread::Prop(prop) = prop else {
+                self.ret.is_complete = false;
+                continue;
+            };
+            let (key, value) = match &**prop {
+                Prop::KeyValue(x) => (&x.key, &*x.value),
+                Prop::Shorthand(x) => {
+                    self.ret.properties.push(PropertyDefinition {
+                        name: x.sym.to_string(),
+                        location: self.location(x.span),
+                        ty: None,
+                        default_value: None,
+                    });
+                    continue;
+                }
+                _ => {
+                    self.ret.is_complete = false;
+                    continue;
+                }
+            };
+            let Some(name) = prop_name_str(key) else {
+                self.ret.is_complete = false;
+                continue;
+            };
+            let (ty, default_value) = match as_object(value) {
+                Some(detail) => {
+                    let mut types = vec![];
+                    if let Some(ty) = find_prop(detail, "type").and_then(type_name) {
+                        types.push(ty);
+                    }
+                    if let Some(items) = find_prop(detail, "optionalTypes").and_then(as_array) {
+                        for ty in items.into_iter().flatten().filter_map(type_name) {
+                            if !types.contains(&ty) {
+                                types.push(ty);
+                            }
+                        }
+                    }
+                    let ty = if types.is_empty() {
+                        None
+                    } else {
+                        Some(types.join(" | "))
+                    };
+                    let default_value =
+                        find_prop(detail, "value").map(|x| self.source_text(x.span()).to_string());
+                    (ty, default_value)
+                }
+                None => (type_name(value).map(|x| x.to_string()), None),
+            };
+            self.ret.properties.push(PropertyDefinition {
+                name: name.to_string(),
+                location: self.location(key.span()),
+                ty,
+                default_value,
+            });
+        }
+    }
+
+    /// Collect methods from an object literal.
+    ///
+    /// If `functions_only` is set, only the entries with function values are treated as methods,
+    /// which is the case for the top-level fields of `Page` calls.
+    fn handle_methods(&mut self, obj: &ObjectLit, functions_only: bool) {
+        for prop in obj.props.iter() {
+            let PropOrSpread::Prop(prop) = prop else {
+                self.ret.is_complete = false;
+                continue;
+            };
+            let key = match &**prop {
+                Prop::KeyValue(x) => {
+                    if functions_only && !matches!(&*x.value, Expr::Fn(_) | Expr::Arrow(_)) {
+                        continue;
+                    }
+                    &x.key
+                }
+                Prop::Method(x) => &x.key,
+                Prop::Shorthand(x) => {
+                    self.ret.methods.push(MethodDefinition {
+                        name: x.sym.to_string(),
+                        location: self.location(x.span),
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            let Some(name) = prop_name_str(key) else {
+                self.ret.is_complete = false;
+                continue;
+            };
+            self.ret.methods.push(MethodDefinition {
+                name: name.to_string(),
+                location: self.location(key.span()),
+            });
+        }
+    }
+
+    fn collect_events(&mut self, syntax: Syntax) {
+        let lexer = Lexer::new(
+            syntax,
+            swc_ecma_ast::EsVersion::EsNext,
+            StringInput::new(self.src, BytePos(0), BytePos(self.src.len() as u32)),
+            None,
+        );
+        let Ok(tokens) = swc_ecma_lexer::lexer(lexer) else {
+            return;
+        };
+        for window in tokens.windows(3) {
+            let [a, b, c] = window else {
+                continue;
+            };
+            let Token::Word(Word::Ident(IdentLike::Other(ident))) = &a.token else {
+                continue;
+            };
+            if ident != "triggerEvent" || b.token != Token::LParen {
+                continue;
+            }
+            if let Token::Str { value, .. } = &c.token {
+                if !self.ret.events.iter().any(|x| x.name == value.as_str()) {
+                    self.ret.events.push(EventDefinition {
+                        name: value.to_string(),
+                        location: self.location(c.span),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl ComponentScript {
+    pub(crate) fn parse(content: &FileContentMetadata, is_ts: bool) -> Self {
+        let src = content.content.as_str();
+        let syntax = if is_ts {
+            Syntax::Typescript(Default::default())
+        } else {
+            Syntax::Es(Default::default())
+        };
+        let mut parser = ScriptParser {
+            src,
+            content,
+            ret: Self::default(),
+        };
+        let lexer = Lexer::new(
+            syntax,
+            swc_ecma_ast::EsVersion::EsNext,
+            StringInput::new(src, BytePos(0), BytePos(src.len() as u32)),
+            None,
+        );
+        let Ok(program) = Parser::new_from(lexer).parse_program() else {
+            return parser.ret;
+        };
+        parser.ret.is_complete = true;
+        let imported = collect_imported_bindings(&program);
+        let mut definitions = vec![];
+        let mut local_behaviors = vec![];
+        let mut has_opaque_definition = false;
+        for_each_definition(&program, |ctor, binding, def| {
+            if ctor == "Behavior" {
+                if let Some(binding) = binding {
+                    local_behaviors.push(binding.to_string());
+                }
+            }
+            match def {
+                Some(def) => definitions.push((ctor.to_string(), def)),
+                None => has_opaque_definition = true,
+            }
+        });
+        // the definitions cannot be fully collected if any of them is not a literal
+        if definitions.is_empty() || has_opaque_definition {
+            parser.ret.is_complete = false;
+        }
+        for (ctor, def) in definitions {
+            parser.handle_definition(&ctor, def, &imported, &local_behaviors);
+        }
+        parser.collect_events(syntax);
+        parser.ret
+    }
+}
+
+enum BehaviorSource {
+    Local(String),
+    Module(String),
+}
+
+fn behavior_source(expr: &Expr, imported: &HashMap<String, String>) -> Option<BehaviorSource> {
+    match expr {
+        Expr::Ident(x) => match imported.get(x.sym.as_str()) {
+            Some(src) => Some(BehaviorSource::Module(src.clone())),
+            None => Some(BehaviorSource::Local(x.sym.to_string())),
+        },
+        _ => require_source(expr).map(|x| BehaviorSource::Module(x.to_string())),
+    }
+}
+
+/// Get the module specifier if the expression is a `require("...")` call.
+fn require_source(expr: &Expr) -> Option<&str> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    match &**callee {
+        Expr::Ident(x) if x.sym == "require" => {}
+        _ => return None,
+    }
+    let arg = call.args.first()?;
+    as_str(&arg.expr)
+}
+
+/// Collect the top-level bindings which are imported from other modules.
+fn collect_imported_bindings(program: &Program) -> HashMap<String, String> {
+    fn handle_stmt(stmt: &Stmt, ret: &mut HashMap<String, String>) {
+        let Stmt::Decl(Decl::Var(x)) = stmt else {
+            return;
+        };
+        for decl in x.decls.iter() {
+            let (Pat::Ident(binding), Some(init)) = (&decl.name, decl.init.as_ref()) else {
+                continue;
+            };
+            if let Some(src) = require_source(init) {
+                ret.insert(binding.id.sym.to_string(), src.to_string());
+            }
+        }
+    }
+    let mut ret = HashMap::new();
+    match program {
+        Program::Module(module) => {
+            for item in module.body.iter() {
+                match item {
+                    ModuleItem::Stmt(stmt) => handle_stmt(stmt, &mut ret),
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(x)) => {
+                        for spec in x.specifiers.iter() {
+                            let local = match spec {
+                                swc_ecma_ast::ImportSpecifier::Named(x) => &x.local,
+                                swc_ecma_ast::ImportSpecifier::Default(x) => &x.local,
+                                swc_ecma_ast::ImportSpecifier::Namespace(x) => &x.local,
+                            };
+                            ret.insert(local.sym.to_string(), x.src.value.to_string());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Program::Script(script) => {
+            for stmt in script.body.iter() {
+                handle_stmt(stmt, &mut ret);
+            }
+        }
+    }
+    ret
+}
+
+/// Iterate over the object literals passed to `Component`, `Behavior` and `Page` calls.
+///
+/// The variable name is also provided if the call result is directly assigned to a variable.
+/// The definition is `None` if the argument is not an object literal, e.g. `Component(options)`.
+fn for_each_definition<'a>(
+    program: &'a Program,
+    mut f: impl FnMut(&str, Option<&str>, Option<&'a ObjectLit>),
+) {
+    fn handle_expr<'a>(
+        expr: &'a Expr,
+        binding: Option<&str>,
+        f: &mut impl FnMut(&str, Option<&str>, Option<&'a ObjectLit>),
+    ) {
+        match expr {
+            Expr::Call(call) => {
+                let Callee::Expr(callee) = &call.callee else {
+                    return;
+                };
+                let Expr::Ident(ident) = &**callee else {
+                    return;
+                };
+                match ident.sym.as_str() {
+                    "Component" | "Behavior" | "Page" => {}
+                    _ => return,
+                }
+                let obj = call.args.first().and_then(|arg| as_object(&arg.expr));
+                f(&ident.sym, binding, obj);
+            }
+            Expr::Paren(x) => handle_expr(&x.expr, binding, f),
+            Expr::Assign(x) => handle_expr(&x.right, None, f),
+            Expr::Seq(x) => {
+                for expr in x.exprs.iter() {
+                    handle_expr(expr, None, f);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn handle_decl<'a>(
+        decl: &'a Decl,
+        f: &mut impl FnMut(&str, Option<&str>, Option<&'a ObjectLit>),
+    ) {
+        if let Decl::Var(x) = decl {
+            for decl in x.decls.iter() {
+                if let Some(init) = decl.init.as_ref() {
+                    let binding = match &decl.name {
+                        Pat::Ident(x) => Some(x.id.sym.as_str()),
+                        _ => None,
+                    };
+                    handle_expr(init, binding, f);
+                }
+            }
+        }
+    }
+    fn handle_stmt<'a>(
+        stmt: &'a Stmt,
+        f: &mut impl FnMut(&str, Option<&str>, Option<&'a ObjectLit>),
+    ) {
+        match stmt {
+            Stmt::Expr(x) => handle_expr(&x.expr, None, f),
+            Stmt::Decl(x) => handle_decl(x, f),
+            _ => {}
+        }
+    }
+    match program {
+        Program::Module(module) => {
+            for item in module.body.iter() {
+                match item {
+                    ModuleItem::Stmt(stmt) => handle_stmt(stmt, &mut f),
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(x)) => {
+                        handle_expr(&x.expr, None, &mut f)
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(x)) => {
+                        handle_decl(&x.decl, &mut f)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Program::Script(script) => {
+            for stmt in script.body.iter() {
+                handle_stmt(stmt, &mut f);
+            }
+        }
+    }
+}
+
+fn prop_name_str(name: &PropName) -> Option<&str> {
+    match name {
+        PropName::Ident(x) => Some(&x.sym),
+        PropName::Str(x) => Some(&x.value),
+        _ => None,
+    }
+}
+
+fn find_prop<'a>(obj: &'a ObjectLit, name: &str) -> Option<&'a Expr> {
+    obj.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        match &**prop {
+            Prop::KeyValue(x) if prop_name_str(&x.key) == Some(name) => Some(&*x.value),
+            _ => None,
+        }
+    })
+}
+
+fn as_object(expr: &Expr) -> Option<&ObjectLit> {
+    match expr {
+        Expr::Object(x) => Some(x),
+        Expr::Paren(x) => as_object(&x.expr),
+        Expr::TsAs(x) => as_object(&x.expr),
+        Expr::TsSatisfies(x) => as_object(&x.expr),
+        _ => None,
+    }
+}
+
+fn as_array(expr: &Expr) -> Option<Vec<Option<&Expr>>> {
+    match expr {
+        Expr::Array(x) => Some(
+            x.elems
+                .iter()
+                .map(|x| x.as_ref().filter(|x| x.spread.is_none()).map(|x| &*x.expr))
+                .collect(),
+        ),
+        Expr::Paren(x) => as_array(&x.expr),
+        Expr::TsAs(x) => as_array(&x.expr),
+        _ => None,
+    }
+}
+
+fn as_bool(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(Lit::Bool(x)) => Some(x.value),
+        _ => None,
+    }
+}
+
+fn as_str(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Lit(Lit::Str(x)) => Some(&x.value),
+        _ => None,
+    }
+}
+
+/// Get the type name of a property type constructor such as `String` or `Number`.
+fn type_name(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Ident(x) => match x.sym.as_str() {
+            "String" => Some("string"),
+            "Number" => Some("number"),
+            "Boolean" => Some("boolean"),
+            "Object" => Some("object"),
+            "Array" => Some("array"),
+            "Function" => Some("function"),
+            _ => None,
+        },
+        Expr::Lit(Lit::Null(_)) => Some("any"),
+        _ => None,
+    }
+}
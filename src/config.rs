@@ -0,0 +1,754 @@
+//! The project-level configuration file and the server settings.
+
+use std::path::{Path, PathBuf};
+
+use glass_easel_template_compiler::stringify::StringifyOptions;
+use lsp_types::{
+    ConfigurationItem, ConfigurationParams, Diagnostic, DiagnosticSeverity,
+    DidChangeConfigurationParams, DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher,
+    GlobPattern, OneOf, PublishDiagnosticsParams, Registration, RegistrationParams,
+    RelativePattern, Unregistration, UnregistrationParams, Url, WatchKind,
+};
+
+use crate::{
+    context::{
+        backend_configuration::BackendConfig, project::FileContentMetadata, ClientSupport,
+        FileLang, ServerContext, ServerContextOptions,
+    },
+    utils::log_if_err,
+};
+
+/// The file name of the project-level configuration file.
+pub(crate) const CONFIG_FILE_NAME: &str = "glass-easel-analyzer.toml";
+
+/// The configuration section name for `workspace/configuration`.
+const CLIENT_SETTINGS_SECTION: &str = "glass-easel-analyzer";
+
+/// The registration id of the backend configuration file watcher.
+const BACKEND_CONFIG_WATCHER_ID: &str = "glassEaselAnalyzer/backendConfigWatcher";
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct ProjectConfig {
+    /// The backend configuration file, relative to the configuration file.
+    #[serde(default)]
+    pub(crate) backend_config: Option<String>,
+    /// The backend configuration files applied on the base one in order, relative to the configuration file.
+    #[serde(default)]
+    pub(crate) backend_config_overlays: Vec<String>,
+    /// The directories to skip when searching projects, relative to the configuration file.
+    #[serde(default)]
+    pub(crate) ignore_paths: Vec<String>,
+    #[serde(default)]
+    pub(crate) enable_other_ss: Option<bool>,
+    #[serde(default)]
+    pub(crate) format: FormatConfig,
+}
+
+/// The `[format]` section.
+///
+/// Missing fields fallback to the formatter defaults.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct FormatConfig {
+    pub(crate) tab_size: Option<u32>,
+    pub(crate) use_tabs: Option<bool>,
+    pub(crate) line_width: Option<u32>,
+    pub(crate) single_quote: Option<bool>,
+    pub(crate) lowercase_hex_colors: Option<bool>,
+    pub(crate) sort_properties: Option<bool>,
+}
+
+impl ProjectConfig {
+    pub(crate) fn parse_str(s: &str) -> anyhow::Result<Self> {
+        let ret = toml::from_str(s)?;
+        Ok(ret)
+    }
+
+    /// Find the nearest configuration file in `dir` or its ancestors.
+    pub(crate) fn find(dir: &Path) -> anyhow::Result<Option<(PathBuf, Self)>> {
+        for p in dir.ancestors() {
+            let config_path = p.join(CONFIG_FILE_NAME);
+            if !config_path.is_file() {
+                continue;
+            }
+            let s = std::fs::read_to_string(&config_path)
+                .map_err(|err| anyhow::anyhow!("failed to read {:?}: {}", config_path, err))?;
+            let config = Self::parse_str(&s)
+                .map_err(|err| anyhow::anyhow!("failed to parse {:?}: {}", config_path, err))?;
+            return Ok(Some((config_path, config)));
+        }
+        Ok(None)
+    }
+}
+
+impl FormatConfig {
+    /// Override fields with the ones in `other` if present.
+    pub(crate) fn merge(&mut self, other: &FormatConfig) {
+        if other.tab_size.is_some() {
+            self.tab_size = other.tab_size;
+        }
+        if other.use_tabs.is_some() {
+            self.use_tabs = other.use_tabs;
+        }
+        if other.line_width.is_some() {
+            self.line_width = other.line_width;
+        }
+        if other.single_quote.is_some() {
+            self.single_quote = other.single_quote;
+        }
+        if other.lowercase_hex_colors.is_some() {
+            self.lowercase_hex_colors = other.lowercase_hex_colors;
+        }
+        if other.sort_properties.is_some() {
+            self.sort_properties = other.sort_properties;
+        }
+    }
+
+    pub(crate) fn stringify_options(&self) -> StringifyOptions {
+        let default = StringifyOptions::default();
+        StringifyOptions {
+            tab_size: self.tab_size.unwrap_or(default.tab_size),
+            use_tab_character: self.use_tabs.unwrap_or(default.use_tab_character),
+            line_width_limit: self.line_width.unwrap_or(default.line_width_limit),
+            expression_string_single_quote: self
+                .single_quote
+                .unwrap_or(default.expression_string_single_quote),
+            ..default
+        }
+    }
+
+    pub(crate) fn wxss_stringify_options(&self) -> crate::wxss::stringify::StringifyOptions {
+        let default = crate::wxss::stringify::StringifyOptions::default();
+        crate::wxss::stringify::StringifyOptions {
+            tab_size: self.tab_size.unwrap_or(default.tab_size),
+            use_tab_character: self.use_tabs.unwrap_or(default.use_tab_character),
+            lowercase_hex_colors: self
+                .lowercase_hex_colors
+                .unwrap_or(default.lowercase_hex_colors),
+            sort_properties: self.sort_properties.unwrap_or(default.sort_properties),
+        }
+    }
+}
+
+/// The settings sent by the client in `workspace/didChangeConfiguration` or `workspace/configuration`.
+///
+/// The field names are the same as the VS Code extension settings.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientSettings {
+    #[serde(default)]
+    backend_configuration_path: Option<String>,
+    #[serde(default)]
+    backend_configuration_overlays: Option<Vec<String>>,
+    #[serde(default)]
+    ignore_paths: Option<Vec<String>>,
+    #[serde(default)]
+    analyze_other_stylesheets: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BackendConfigSource {
+    Content(String),
+    Path(PathBuf),
+}
+
+impl BackendConfigSource {
+    pub(crate) fn load(&self) -> anyhow::Result<BackendConfig> {
+        match self {
+            Self::Content(s) => BackendConfig::parse_str(s),
+            Self::Path(p) => {
+                let s = std::fs::read_to_string(p)
+                    .map_err(|err| anyhow::anyhow!("failed to read {:?}: {}", p, err))?;
+                BackendConfig::parse_str(&s)
+                    .map_err(|err| anyhow::anyhow!("failed to parse {:?}: {}", p, err))
+            }
+        }
+    }
+
+    pub(crate) fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Content(_) => None,
+            Self::Path(p) => Some(p),
+        }
+    }
+}
+
+/// Load a backend configuration file, converting the parsing error into diagnostics of the file.
+fn load_backend_config_file(
+    p: &Path,
+    parse: fn(&str) -> anyhow::Result<BackendConfig>,
+) -> (anyhow::Result<BackendConfig>, Vec<Diagnostic>) {
+    let s = match std::fs::read_to_string(p) {
+        Ok(x) => x,
+        Err(err) => {
+            return (
+                Err(anyhow::anyhow!("failed to read {:?}: {}", p, err)),
+                vec![],
+            )
+        }
+    };
+    let err = match parse(&s) {
+        Ok(x) => return (Ok(x), vec![]),
+        Err(err) => err,
+    };
+    let (range, message) = match err
+        .downcast_ref::<toml::de::Error>()
+        .and_then(|x| Some((x.span()?, x.message())))
+    {
+        Some((span, message)) => {
+            let content = FileContentMetadata::new(s, FileLang::Unknown);
+            let pos = |index: usize| {
+                let (line, utf16_col) =
+                    content.line_utf16_col_for_content_index(index.min(content.content.len()));
+                crate::wxss::Position { line, utf16_col }
+            };
+            (
+                crate::utils::location_to_lsp_range(&(pos(span.start)..pos(span.end))),
+                message.to_string(),
+            )
+        }
+        None => (Default::default(), err.to_string()),
+    };
+    let diag = Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        message,
+        ..Default::default()
+    };
+    (
+        Err(anyhow::anyhow!("failed to parse {:?}: {}", p, err)),
+        vec![diag],
+    )
+}
+
+/// The backend configuration composed of a base one and the overlays applied on it in order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BackendConfigLayers {
+    pub(crate) base: Option<BackendConfigSource>,
+    pub(crate) overlays: Vec<PathBuf>,
+}
+
+impl BackendConfigLayers {
+    /// The backend configuration files which should be watched.
+    pub(crate) fn paths(&self) -> Vec<&Path> {
+        self.base
+            .as_ref()
+            .and_then(|x| x.path())
+            .into_iter()
+            .chain(self.overlays.iter().map(|x| x.as_path()))
+            .collect()
+    }
+
+    /// Load and merge all layers.
+    ///
+    /// Fails if any layer fails.
+    /// The diagnostics of each backend configuration file are also returned,
+    /// including the parsing errors and the conflicts between layers.
+    pub(crate) fn load(
+        &self,
+    ) -> (
+        anyhow::Result<BackendConfig>,
+        Vec<(PathBuf, Vec<Diagnostic>)>,
+    ) {
+        let mut diagnostics = vec![];
+        let mut ret = match self.base.as_ref() {
+            None => {
+                log::warn!("Missing glass-easel backend configuration");
+                Ok(Default::default())
+            }
+            Some(BackendConfigSource::Path(p)) => {
+                let (ret, diags) = load_backend_config_file(p, BackendConfig::parse_str);
+                diagnostics.push((p.clone(), diags));
+                ret
+            }
+            Some(source) => source.load(),
+        };
+        for p in self.overlays.iter() {
+            let (overlay, mut diags) =
+                load_backend_config_file(p, BackendConfig::parse_overlay_str);
+            match (&mut ret, overlay) {
+                (Ok(config), Ok(overlay)) => match config.merge_overlay(overlay) {
+                    Ok(conflicts) => {
+                        for message in conflicts {
+                            log::warn!("Backend configuration conflict in {:?}: {}", p, message);
+                            diags.push(Diagnostic {
+                                severity: Some(DiagnosticSeverity::WARNING),
+                                message,
+                                ..Default::default()
+                            });
+                        }
+                    }
+                    Err(err) => {
+                        diags.push(Diagnostic {
+                            severity: Some(DiagnosticSeverity::ERROR),
+                            message: err.to_string(),
+                            ..Default::default()
+                        });
+                        ret = Err(anyhow::anyhow!("failed to apply {:?}: {}", p, err));
+                    }
+                },
+                (Ok(_), Err(err)) => ret = Err(err),
+                (Err(_), _) => {}
+            }
+            diagnostics.push((p.clone(), diags));
+        }
+        (ret, diagnostics)
+    }
+}
+
+/// A partial set of the server settings from one source.
+///
+/// `None` means the field is not given by the source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SettingsLayer {
+    /// `Some(None)` means the backend configuration is unset explicitly.
+    pub(crate) backend_config: Option<Option<BackendConfigSource>>,
+    pub(crate) backend_config_overlays: Option<Vec<PathBuf>>,
+    pub(crate) ignore_paths: Option<Vec<PathBuf>>,
+    pub(crate) enable_other_ss: Option<bool>,
+    /// The backend configuration content used when no source gives a backend configuration.
+    pub(crate) default_backend_config: Option<String>,
+}
+
+/// Resolve a path in the settings, which can be a `file:` URL or a path relative to `base`.
+pub(crate) fn resolve_setting_path(s: &str, base: Option<&Path>) -> PathBuf {
+    if let Some(p) = Url::parse(s)
+        .ok()
+        .filter(|x| x.scheme() == "file")
+        .and_then(|x| x.to_file_path().ok())
+    {
+        return p;
+    }
+    match base {
+        Some(base) => base.join(s),
+        None => PathBuf::from(s),
+    }
+}
+
+impl SettingsLayer {
+    fn from_project_config(config_path: &Path, config: &ProjectConfig) -> Self {
+        let base = config_path.parent();
+        Self {
+            backend_config: config
+                .backend_config
+                .as_ref()
+                .map(|x| Some(BackendConfigSource::Path(resolve_setting_path(x, base)))),
+            backend_config_overlays: Some(
+                config
+                    .backend_config_overlays
+                    .iter()
+                    .map(|x| resolve_setting_path(x, base))
+                    .collect(),
+            ),
+            ignore_paths: Some(
+                config
+                    .ignore_paths
+                    .iter()
+                    .map(|x| resolve_setting_path(x, base))
+                    .collect(),
+            ),
+            enable_other_ss: config.enable_other_ss,
+            default_backend_config: None,
+        }
+    }
+
+    /// Override fields with the ones given in `other`.
+    pub(crate) fn merge(&mut self, other: SettingsLayer) {
+        if other.backend_config.is_some() {
+            self.backend_config = other.backend_config;
+        }
+        if other.backend_config_overlays.is_some() {
+            self.backend_config_overlays = other.backend_config_overlays;
+        }
+        if other.ignore_paths.is_some() {
+            self.ignore_paths = other.ignore_paths;
+        }
+        if other.enable_other_ss.is_some() {
+            self.enable_other_ss = other.enable_other_ss;
+        }
+        if other.default_backend_config.is_some() {
+            self.default_backend_config = other.default_backend_config;
+        }
+    }
+}
+
+/// The resolved server settings.
+pub(crate) struct ServerSettings {
+    pub(crate) backend_config: BackendConfigLayers,
+    pub(crate) options: ServerContextOptions,
+}
+
+impl ServerSettings {
+    /// Merge the configuration files in the workspace folders and the editor settings.
+    ///
+    /// The editor settings take precedence over the configuration files,
+    /// while the ignored paths are collected from all sources.
+    /// The backend configuration overlays are only taken from the same source as the base one,
+    /// i.e. the overlays of a source without a base backend configuration are ignored.
+    /// The default backend configuration of the editor (with the editor overlays) is used if no source gives one.
+    pub(crate) fn resolve(workspace_folders: &[PathBuf], editor: &SettingsLayer) -> Self {
+        let mut backend_config = None;
+        let mut backend_config_overlays = vec![];
+        let mut ignore_paths = vec![];
+        let mut enable_other_ss = None;
+        let mut visited_config_paths = vec![];
+        for folder in workspace_folders {
+            let (config_path, config) = match ProjectConfig::find(folder) {
+                Ok(Some(x)) => x,
+                Ok(None) => continue,
+                Err(err) => {
+                    log::error!("{}", err);
+                    continue;
+                }
+            };
+            if visited_config_paths.contains(&config_path) {
+                continue;
+            }
+            log::info!("Loaded configuration file {:?}", config_path);
+            let layer = SettingsLayer::from_project_config(&config_path, &config);
+            // the overlays are based on the backend configuration of the same file
+            let overlays = layer.backend_config_overlays.unwrap_or_default();
+            match layer.backend_config.flatten() {
+                Some(x) if backend_config.is_none() => {
+                    backend_config = Some(x);
+                    backend_config_overlays = overlays;
+                }
+                None if !overlays.is_empty() => {
+                    log::warn!(
+                        "The backend configuration overlays in {:?} are ignored without a `backend-config` in the same file",
+                        config_path
+                    );
+                }
+                _ => {}
+            }
+            visited_config_paths.push(config_path);
+            ignore_paths.extend(layer.ignore_paths.unwrap_or_default());
+            enable_other_ss = enable_other_ss.or(layer.enable_other_ss);
+        }
+        let editor_backend_config = match editor.backend_config.as_ref() {
+            Some(Some(x)) => Some(x.clone()),
+            _ if backend_config.is_none() => editor
+                .default_backend_config
+                .clone()
+                .map(BackendConfigSource::Content),
+            _ => None,
+        };
+        let editor_overlays = editor.backend_config_overlays.clone().unwrap_or_default();
+        if editor_backend_config.is_some() {
+            backend_config = editor_backend_config;
+            backend_config_overlays = editor_overlays;
+        } else if !editor_overlays.is_empty() {
+            log::warn!(
+                "The backend configuration overlays in the editor settings are ignored without a backend configuration in the editor settings"
+            );
+        }
+        ignore_paths.extend(editor.ignore_paths.iter().flatten().cloned());
+        if editor.enable_other_ss.is_some() {
+            enable_other_ss = editor.enable_other_ss;
+        }
+        Self {
+            backend_config: BackendConfigLayers {
+                base: backend_config,
+                overlays: backend_config_overlays,
+            },
+            options: ServerContextOptions {
+                ignore_paths,
+                enable_other_ss: enable_other_ss.unwrap_or(false),
+            },
+        }
+    }
+
+    /// Load the backend configuration, falling back to the default one on error.
+    ///
+    /// The diagnostics of the backend configuration files are also returned.
+    pub(crate) fn load_backend_config(&self) -> (BackendConfig, Vec<(PathBuf, Vec<Diagnostic>)>) {
+        let (ret, diagnostics) = self.backend_config.load();
+        match ret {
+            Ok(x) => (x, diagnostics),
+            Err(err) => {
+                log::error!("Failed to load glass-easel backend configuration: {}", err);
+                (Default::default(), diagnostics)
+            }
+        }
+    }
+}
+
+/// Publish the diagnostics of the backend configuration files.
+///
+/// If the client pulls the diagnostics, they are stored and the client is asked to pull again.
+pub(crate) fn publish_backend_config_diagnostics(
+    ctx: &ServerContext,
+    diagnostics: Vec<(PathBuf, Vec<Diagnostic>)>,
+) {
+    if ctx.client_support().pull_diagnostics {
+        for (p, diagnostics) in diagnostics {
+            ctx.set_backend_config_file_diagnostics(&p, Some(diagnostics));
+        }
+        ctx.diagnostics_changed();
+        crate::file::request_diagnostic_refresh(ctx);
+        return;
+    }
+    for (p, diagnostics) in diagnostics {
+        let Ok(uri) = Url::from_file_path(&p) else {
+            continue;
+        };
+        log_if_err(ctx.send_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            },
+        ));
+    }
+}
+
+/// Clear the diagnostics of the backend configuration files no longer used.
+fn clear_backend_config_diagnostics(ctx: &ServerContext, paths: &[&Path]) {
+    if ctx.client_support().pull_diagnostics {
+        for p in paths {
+            ctx.set_backend_config_file_diagnostics(p, None);
+        }
+        ctx.diagnostics_changed();
+        crate::file::request_diagnostic_refresh(ctx);
+        return;
+    }
+    let diagnostics = paths.iter().map(|p| (p.to_path_buf(), vec![])).collect();
+    publish_backend_config_diagnostics(ctx, diagnostics);
+}
+
+/// The file watcher registration for the backend configuration files.
+///
+/// The backend configuration files may be outside the workspace folders,
+/// so they are watched separately.
+pub(crate) fn backend_config_watcher_registration(
+    client_support: &ClientSupport,
+    paths: &[&Path],
+) -> Option<Registration> {
+    if !client_support.watched_files_registration || paths.is_empty() {
+        return None;
+    }
+    let watchers = paths
+        .iter()
+        .filter_map(|p| {
+            let glob_pattern = match (p.parent(), p.file_name().and_then(|x| x.to_str())) {
+                (Some(dir), Some(file_name)) if client_support.relative_pattern => {
+                    let base_uri = Url::from_directory_path(dir).ok()?;
+                    GlobPattern::Relative(RelativePattern {
+                        base_uri: OneOf::Right(base_uri),
+                        pattern: file_name.to_string(),
+                    })
+                }
+                _ => GlobPattern::String(p.to_str()?.to_string()),
+            };
+            Some(FileSystemWatcher {
+                glob_pattern,
+                kind: Some(WatchKind::all()),
+            })
+        })
+        .collect();
+    let options = DidChangeWatchedFilesRegistrationOptions { watchers };
+    Some(Registration {
+        id: BACKEND_CONFIG_WATCHER_ID.to_string(),
+        method: "workspace/didChangeWatchedFiles".to_string(),
+        register_options: Some(serde_json::to_value(options).ok()?),
+    })
+}
+
+/// Use other backend configuration layers and watch the files of them.
+///
+/// The diagnostics of the files no longer used are cleared.
+fn update_backend_config_layers(ctx: &ServerContext, layers: BackendConfigLayers) {
+    let old = ctx.set_backend_config_layers(layers.clone());
+    let old_paths = old.paths();
+    let paths = layers.paths();
+    if old_paths == paths {
+        return;
+    }
+    let unused: Vec<_> = old_paths
+        .iter()
+        .filter(|p| !paths.contains(p))
+        .copied()
+        .collect();
+    if !unused.is_empty() {
+        clear_backend_config_diagnostics(ctx, &unused);
+    }
+    if !ctx.client_support().watched_files_registration {
+        return;
+    }
+    let registration = backend_config_watcher_registration(ctx.client_support(), &paths);
+    let registered = !old_paths.is_empty();
+    let ctx = ctx.clone();
+    tokio::task::spawn_local(async move {
+        if let Err(err) = update_backend_config_watcher(&ctx, registered, registration).await {
+            log::error!("Failed to watch glass-easel backend configuration: {}", err);
+        }
+    });
+}
+
+async fn update_backend_config_watcher(
+    ctx: &ServerContext,
+    registered: bool,
+    registration: Option<Registration>,
+) -> anyhow::Result<()> {
+    if registered {
+        let params = UnregistrationParams {
+            unregisterations: vec![Unregistration {
+                id: BACKEND_CONFIG_WATCHER_ID.to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+            }],
+        };
+        ctx.send_request("client/unregisterCapability", serde_json::to_value(params)?)
+            .await?;
+    }
+    if let Some(registration) = registration {
+        let params = RegistrationParams {
+            registrations: vec![registration],
+        };
+        ctx.send_request("client/registerCapability", serde_json::to_value(params)?)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Use a new backend configuration and refresh everything depends on it.
+async fn apply_backend_config(ctx: &ServerContext, backend_config: BackendConfig) {
+    let template_backend_config = backend_config.generate_template_backend_config();
+    ctx.set_backend_config(backend_config);
+    log_if_err(ctx.send_notification(
+        "glassEaselAnalyzer/templateBackendConfig",
+        crate::TemplateBackendConfigInfo {
+            content: template_backend_config,
+        },
+    ));
+    crate::file::republish_all_diagnostics(ctx).await;
+    if ctx.client_support().semantic_tokens_refresh {
+        // the response can only be received after the current message handled
+        let ctx = ctx.clone();
+        tokio::task::spawn_local(async move {
+            if let Err(err) = ctx
+                .send_request("workspace/semanticTokens/refresh", serde_json::Value::Null)
+                .await
+            {
+                log::error!("{}", err);
+            }
+        });
+    }
+}
+
+/// Reparse the backend configuration files after one of them or the settings changed.
+///
+/// The previous backend configuration is kept if the new one is invalid.
+pub(crate) async fn reload_backend_config(ctx: &ServerContext) {
+    let layers = ctx.backend_config_layers();
+    log::info!("Reloading glass-easel backend configuration");
+    let (ret, diagnostics) = layers.load();
+    publish_backend_config_diagnostics(ctx, diagnostics);
+    match ret {
+        Ok(backend_config) => apply_backend_config(ctx, backend_config).await,
+        Err(err) => {
+            log::error!(
+                "Failed to reload glass-easel backend configuration (the previous one is still used): {}",
+                err
+            );
+        }
+    }
+}
+
+fn client_settings_layer(value: serde_json::Value, base: Option<&Path>) -> SettingsLayer {
+    let settings: ClientSettings = match serde_json::from_value(value) {
+        Ok(x) => x,
+        Err(err) => {
+            log::error!("Invalid settings: {}", err);
+            return Default::default();
+        }
+    };
+    // an empty path unsets the backend configuration
+    SettingsLayer {
+        backend_config: settings.backend_configuration_path.map(|x| {
+            (!x.is_empty()).then(|| BackendConfigSource::Path(resolve_setting_path(&x, base)))
+        }),
+        backend_config_overlays: settings
+            .backend_configuration_overlays
+            .map(|list| list.iter().map(|x| resolve_setting_path(x, base)).collect()),
+        ignore_paths: settings
+            .ignore_paths
+            .map(|list| list.iter().map(|x| resolve_setting_path(x, base)).collect()),
+        enable_other_ss: settings.analyze_other_stylesheets,
+        default_backend_config: None,
+    }
+}
+
+/// Re-resolve the settings and apply the changes.
+///
+/// Projects are reloaded if the project searching options changed.
+pub(crate) async fn reload_settings(mut ctx: ServerContext) {
+    ctx.clear_project_configs();
+    let workspace_folders = ctx.workspace_folders();
+    let settings = ServerSettings::resolve(&workspace_folders, &ctx.editor_settings());
+    update_backend_config_layers(&ctx, settings.backend_config.clone());
+    reload_backend_config(&ctx).await;
+    if *ctx.options() != settings.options {
+        log::info!("Project searching options changed, reloading projects");
+        ctx.set_options(settings.options);
+        ctx.clear_all_projects().await;
+        for folder in workspace_folders {
+            crate::file::load_workspace_folder(&mut ctx, &folder).await;
+        }
+    }
+}
+
+async fn fetch_client_settings(ctx: &ServerContext) -> anyhow::Result<serde_json::Value> {
+    let params = ConfigurationParams {
+        items: vec![ConfigurationItem {
+            scope_uri: None,
+            section: Some(CLIENT_SETTINGS_SECTION.to_string()),
+        }],
+    };
+    let ret = ctx
+        .send_request("workspace/configuration", serde_json::to_value(params)?)
+        .await?;
+    let mut list: Vec<serde_json::Value> = serde_json::from_value(ret)?;
+    Ok(list.pop().unwrap_or_default())
+}
+
+/// Use the settings sent by the client, and apply them if changed.
+async fn apply_client_settings(ctx: ServerContext, value: serde_json::Value) {
+    let base = ctx.workspace_folders().into_iter().next();
+    let layer = client_settings_layer(value, base.as_deref());
+    if ctx.update_editor_settings(layer) {
+        reload_settings(ctx).await;
+    }
+}
+
+/// Fetch the settings with `workspace/configuration` and apply them.
+///
+/// The response can only be received after the current message handled,
+/// so this should be spawned as a separate task.
+pub(crate) async fn pull_client_settings(ctx: ServerContext) {
+    match fetch_client_settings(&ctx).await {
+        Ok(value) => apply_client_settings(ctx, value).await,
+        Err(err) => log::error!("Failed to fetch settings: {}", err),
+    }
+}
+
+pub(crate) async fn did_change_configuration(
+    ctx: ServerContext,
+    params: DidChangeConfigurationParams,
+) -> anyhow::Result<()> {
+    match params.settings {
+        serde_json::Value::Object(mut map) => {
+            let value = match map.remove(CLIENT_SETTINGS_SECTION) {
+                Some(x) => x,
+                None => serde_json::Value::Object(map),
+            };
+            tokio::task::spawn_local(apply_client_settings(ctx, value));
+        }
+        _ if ctx.client_support().configuration => {
+            tokio::task::spawn_local(pull_client_settings(ctx));
+        }
+        _ => {}
+    }
+    Ok(())
+}
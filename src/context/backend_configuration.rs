@@ -27,6 +27,8 @@ pub(crate) struct BackendConfig {
     pub(crate) pseudo_element: Vec<PseudoElementConfig>,
     #[serde(default)]
     pub(crate) style_property: Vec<StylePropertyConfig>,
+    #[serde(default)]
+    pub(crate) style_function: Vec<StyleFunctionConfig>,
 }
 
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -65,7 +67,15 @@ pub(crate) struct ElementConfig {
 pub(crate) struct AttributeConfig {
     pub(crate) name: String,
     #[serde(default)]
-    pub(crate) ty: String,
+    pub(crate) ty: PropertyType,
+    #[serde(default)]
+    pub(crate) default: Option<toml::Value>,
+    #[serde(default)]
+    pub(crate) required: bool,
+    #[serde(default)]
+    pub(crate) since: Option<ConfigVersion>,
+    #[serde(default)]
+    pub(crate) until: Option<ConfigVersion>,
     #[serde(default)]
     pub(crate) description: String,
     #[serde(default)]
@@ -76,6 +86,219 @@ pub(crate) struct AttributeConfig {
     pub(crate) deprecated: bool,
 }
 
+/// The type of an attribute or a property.
+///
+/// Written as `boolean` `number` `string` `object` `array` `any`,
+/// quoted string literals (an enum), or a union of them separated by `|`,
+/// e.g. `"'default' | 'primary' | 'warn'"` or `"string | number"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum PropertyType {
+    #[default]
+    Any,
+    Boolean,
+    Number,
+    String,
+    Object,
+    Array,
+    Enum(Vec<String>),
+    Union(Vec<PropertyType>),
+}
+
+impl std::str::FromStr for PropertyType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Self::Any);
+        }
+        let mut members = vec![];
+        let mut literals = vec![];
+        for part in s.split('|') {
+            let part = part.trim();
+            let member = match part {
+                "any" => Self::Any,
+                "boolean" => Self::Boolean,
+                "number" => Self::Number,
+                "string" => Self::String,
+                "object" => Self::Object,
+                "array" => Self::Array,
+                "" => return Err(format!("empty member in type `{}`", s)),
+                _ => {
+                    let literal = part
+                        .strip_prefix('\'')
+                        .and_then(|x| x.strip_suffix('\''))
+                        .or_else(|| part.strip_prefix('"').and_then(|x| x.strip_suffix('"')));
+                    match literal {
+                        Some(x) if part.len() >= 2 => {
+                            literals.push(x.to_string());
+                            continue;
+                        }
+                        _ => return Err(format!("unknown type `{}`", part)),
+                    }
+                }
+            };
+            if !members.contains(&member) {
+                members.push(member);
+            }
+        }
+        if !literals.is_empty() {
+            members.push(Self::Enum(literals));
+        }
+        if members.len() == 1 {
+            Ok(members.pop().unwrap())
+        } else {
+            Ok(Self::Union(members))
+        }
+    }
+}
+
+impl std::fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::Number => write!(f, "number"),
+            Self::String => write!(f, "string"),
+            Self::Object => write!(f, "object"),
+            Self::Array => write!(f, "array"),
+            Self::Enum(list) => {
+                for (i, x) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{:?}", x)?;
+                }
+                Ok(())
+            }
+            Self::Union(list) => {
+                for (i, x) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", x)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PropertyType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl PropertyType {
+    /// The TypeScript type used in the template backend configuration.
+    pub(crate) fn ts_type(&self) -> String {
+        match self {
+            Self::Array => "any[]".to_string(),
+            Self::Union(list) => list
+                .iter()
+                .map(|x| x.ts_type())
+                .collect::<Vec<_>>()
+                .join(" | "),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Check whether a TOML value (e.g. a default value) matches this type.
+    pub(crate) fn accepts(&self, value: &toml::Value) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Boolean => value.is_bool(),
+            Self::Number => value.is_integer() || value.is_float(),
+            Self::String => value.is_str(),
+            Self::Object => value.is_table(),
+            Self::Array => value.is_array(),
+            Self::Enum(list) => value.as_str().is_some_and(|x| list.iter().any(|y| x == y)),
+            Self::Union(list) => list.iter().any(|x| x.accepts(value)),
+        }
+    }
+
+    /// The allowed values if the type only accepts string literals.
+    pub(crate) fn enum_values(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::Enum(list) => Some(list.iter().map(|x| x.as_str()).collect()),
+            Self::Union(list) => {
+                let mut ret = vec![];
+                for x in list {
+                    ret.append(&mut x.enum_values()?);
+                }
+                Some(ret)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A dotted version number of the backend, e.g. `2.10.4`, used in `since` and `until`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ConfigVersion(Vec<u32>);
+
+impl std::str::FromStr for ConfigVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let list = s
+            .split('.')
+            .map(|x| x.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("invalid version `{}`", s))?;
+        Ok(Self(list))
+    }
+}
+
+impl std::fmt::Display for ConfigVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, x) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", x)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ConfigVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl AttributeConfig {
+    /// Check the metadata which cannot be checked while deserializing.
+    fn check(&self, scope: &str) -> anyhow::Result<()> {
+        if let Some(default) = self.default.as_ref() {
+            if !self.ty.accepts(default) {
+                return Err(anyhow::anyhow!(
+                    "the default value `{}` of `{}`{} does not match the type `{}`",
+                    default,
+                    self.name,
+                    scope,
+                    self.ty,
+                ));
+            }
+        }
+        if let (Some(since), Some(until)) = (self.since.as_ref(), self.until.as_ref()) {
+            if since > until {
+                return Err(anyhow::anyhow!(
+                    "`{}`{} is available since {} but removed in an earlier version {}",
+                    self.name,
+                    scope,
+                    since,
+                    until,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub(crate) struct ComponentConfig {
@@ -116,14 +339,11 @@ pub(crate) struct EventConfig {
 
 impl BackendConfig {
     pub(crate) fn parse_str(s: &str) -> anyhow::Result<Self> {
-        let config: Self = toml::from_str(s)?;
-        let major_version = config.glass_easel_backend_config.major_version;
-        if major_version < 1 {
+        let config = Self::parse_overlay_str(s)?;
+        if config.glass_easel_backend_config.major_version < 1 {
             log::warn!(
                 "This backend configuration may be problematic. Please check the updates of it."
             );
-        } else if major_version > 1 {
-            Err(anyhow::Error::msg("The backend configuration is designed for a later version of glass-easel-analyzer."))?;
         }
         log::info!(
             "Loaded backend configuration: {}",
@@ -132,6 +352,242 @@ impl BackendConfig {
         Ok(config)
     }
 
+    /// Parse a backend configuration which is applied on another one with `merge_overlay`.
+    ///
+    /// The `[glass-easel-backend-config]` section can be omitted in overlays.
+    pub(crate) fn parse_overlay_str(s: &str) -> anyhow::Result<Self> {
+        let config: Self = toml::from_str(s)?;
+        if config.glass_easel_backend_config.major_version > 1 {
+            Err(anyhow::Error::msg("The backend configuration is designed for a later version of glass-easel-analyzer."))?;
+        }
+        for attr in config.global_attribute.iter() {
+            attr.check("")?;
+        }
+        for elem in config.element.iter() {
+            let scope = format!(" in element <{}>", elem.tag_name);
+            for attr in elem.attribute.iter() {
+                attr.check(&scope)?;
+            }
+        }
+        for comp in config.component.iter() {
+            let scope = format!(" in component <{}>", comp.tag_name);
+            for prop in comp.property.iter() {
+                prop.check(&scope)?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Apply an overlay on this backend configuration.
+    ///
+    /// Entries in the overlay are added, or merged into the existing ones with the same name:
+    /// non-empty descriptions and references override the existing ones,
+    /// and `deprecated` marks the existing ones deprecated.
+    /// Returns the conflicts found, in which cases the overlay takes precedence
+    /// (except the tags defined as both elements and components, which are ignored in the overlay).
+    pub(crate) fn merge_overlay(&mut self, overlay: BackendConfig) -> anyhow::Result<Vec<String>> {
+        let mut conflicts = vec![];
+
+        // check versions
+        let base_version = &self.glass_easel_backend_config;
+        let overlay_version = &overlay.glass_easel_backend_config;
+        if base_version.major_version > 0 && overlay_version.major_version > 0 {
+            if base_version.major_version != overlay_version.major_version {
+                return Err(anyhow::anyhow!(
+                    "the overlay is designed for backend configuration version {}.x, but the base one is {}.{}",
+                    overlay_version.major_version,
+                    base_version.major_version,
+                    base_version.minor_version,
+                ));
+            }
+            if base_version.minor_version < overlay_version.minor_version {
+                conflicts.push(format!(
+                    "the overlay is designed for backend configuration version {}.{}, but the base one is {}.{}",
+                    overlay_version.major_version,
+                    overlay_version.minor_version,
+                    base_version.major_version,
+                    base_version.minor_version,
+                ));
+            }
+        } else if base_version.major_version == 0 {
+            self.glass_easel_backend_config.major_version = overlay_version.major_version;
+            self.glass_easel_backend_config.minor_version = overlay_version.minor_version;
+        }
+
+        // tags cannot change between elements and components
+        let mut element = vec![];
+        for elem in overlay.element {
+            if self.search_component(&elem.tag_name).is_some() {
+                conflicts.push(format!(
+                    "<{}> is a component in the base configuration but an element in the overlay",
+                    elem.tag_name
+                ));
+            } else {
+                element.push(elem);
+            }
+        }
+        let mut component = vec![];
+        for comp in overlay.component {
+            if self.search_element(&comp.tag_name).is_some() {
+                conflicts.push(format!(
+                    "<{}> is an element in the base configuration but a component in the overlay",
+                    comp.tag_name
+                ));
+            } else {
+                component.push(comp);
+            }
+        }
+
+        merge_layer_items(&mut self.element, element, "", &mut conflicts);
+        merge_layer_items(&mut self.component, component, "", &mut conflicts);
+        merge_layer_items(
+            &mut self.global_attribute,
+            overlay.global_attribute,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(
+            &mut self.global_event,
+            overlay.global_event,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(&mut self.media_type, overlay.media_type, "", &mut conflicts);
+        merge_layer_items(
+            &mut self.media_feature,
+            overlay.media_feature,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(
+            &mut self.pseudo_class,
+            overlay.pseudo_class,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(
+            &mut self.pseudo_element,
+            overlay.pseudo_element,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(
+            &mut self.style_property,
+            overlay.style_property,
+            "",
+            &mut conflicts,
+        );
+        merge_layer_items(
+            &mut self.style_function,
+            overlay.style_function,
+            "",
+            &mut conflicts,
+        );
+        Ok(conflicts)
+    }
+
+    /// Find problems which are not parsing errors, e.g. duplicated names.
+    pub(crate) fn validate(&self) -> Vec<String> {
+        fn check_duplicated<'a>(
+            ret: &mut Vec<String>,
+            kind: &str,
+            scope: &str,
+            names: impl Iterator<Item = &'a str>,
+        ) {
+            let mut visited: Vec<&str> = vec![];
+            for name in names {
+                if visited.contains(&name) {
+                    ret.push(format!("duplicated {} `{}`{}", kind, name, scope));
+                } else {
+                    visited.push(name);
+                }
+            }
+        }
+
+        let mut ret = vec![];
+        if self.glass_easel_backend_config.major_version < 1 {
+            ret.push(format!(
+                "`glass-easel-backend-config.major-version` should be 1 (found {})",
+                self.glass_easel_backend_config.major_version
+            ));
+        }
+        check_duplicated(
+            &mut ret,
+            "element",
+            "",
+            self.element.iter().map(|x| x.tag_name.as_str()),
+        );
+        check_duplicated(
+            &mut ret,
+            "component",
+            "",
+            self.component.iter().map(|x| x.tag_name.as_str()),
+        );
+        for elem in self.element.iter() {
+            if self.search_component(&elem.tag_name).is_some() {
+                ret.push(format!(
+                    "<{}> is defined as both an element and a component",
+                    elem.tag_name
+                ));
+            }
+        }
+        check_duplicated(
+            &mut ret,
+            "global attribute",
+            "",
+            self.global_attribute.iter().map(|x| x.name.as_str()),
+        );
+        check_duplicated(
+            &mut ret,
+            "global event",
+            "",
+            self.global_event.iter().map(|x| x.name.as_str()),
+        );
+        for elem in self.element.iter() {
+            let scope = format!(" in element <{}>", elem.tag_name);
+            check_duplicated(
+                &mut ret,
+                "attribute",
+                &scope,
+                elem.attribute.iter().map(|x| x.name.as_str()),
+            );
+            check_duplicated(
+                &mut ret,
+                "event",
+                &scope,
+                elem.event.iter().map(|x| x.name.as_str()),
+            );
+        }
+        for comp in self.component.iter() {
+            let scope = format!(" in component <{}>", comp.tag_name);
+            check_duplicated(
+                &mut ret,
+                "property",
+                &scope,
+                comp.property.iter().map(|x| x.name.as_str()),
+            );
+            check_duplicated(
+                &mut ret,
+                "event",
+                &scope,
+                comp.event.iter().map(|x| x.name.as_str()),
+            );
+        }
+        check_duplicated(
+            &mut ret,
+            "style property",
+            "",
+            self.style_property.iter().map(|x| x.name.as_str()),
+        );
+        check_duplicated(
+            &mut ret,
+            "style function",
+            "",
+            self.style_function.iter().map(|x| x.name.as_str()),
+        );
+        ret
+    }
+
     pub(crate) fn search_element(&self, tag_name: &str) -> Option<&ElementConfig> {
         self.element.iter().find(|x| x.tag_name == tag_name)
     }
@@ -300,6 +756,26 @@ pub(crate) struct StylePropertyConfig {
     pub(crate) reference: Option<Url>,
 }
 
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct StyleFunctionConfig {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) parameter: Vec<StyleFunctionParameterConfig>,
+    #[serde(default)]
+    pub(crate) description: String,
+    #[serde(default)]
+    pub(crate) reference: Option<Url>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct StyleFunctionParameterConfig {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: String,
+}
+
 impl BackendConfig {
     pub(crate) fn extract_template_backend_config(&self, mut w: impl Write) -> std::fmt::Result {
         let config = self;
@@ -323,8 +799,12 @@ impl BackendConfig {
         // write global attributes
         writeln!(w, r#"type GlobalAttributes = {{"#)?;
         for attr in &config.global_attribute {
-            let ty = if attr.ty.is_empty() { "any" } else { &attr.ty };
-            writeln!(w, r#"{:?}: {}"#, dash_to_camel(&attr.name), ty)?;
+            writeln!(
+                w,
+                r#"{:?}: {}"#,
+                dash_to_camel(&attr.name),
+                attr.ty.ts_type()
+            )?;
         }
         writeln!(w, r#"}}"#)?;
 
@@ -333,16 +813,24 @@ impl BackendConfig {
         for elem in &config.element {
             writeln!(w, r#"{:?}: GlobalAttributes & {{"#, elem.tag_name)?;
             for attr in &elem.attribute {
-                let ty = if attr.ty.is_empty() { "any" } else { &attr.ty };
-                writeln!(w, r#"{:?}: {}"#, dash_to_camel(&attr.name), ty)?;
+                writeln!(
+                    w,
+                    r#"{:?}: {}"#,
+                    dash_to_camel(&attr.name),
+                    attr.ty.ts_type()
+                )?;
             }
             writeln!(w, r#"}}"#)?;
         }
         for comp in &config.component {
             writeln!(w, r#"{:?}: GlobalAttributes & {{"#, comp.tag_name)?;
             for prop in &comp.property {
-                let ty = if prop.ty.is_empty() { "any" } else { &prop.ty };
-                writeln!(w, r#"{:?}: {}"#, dash_to_camel(&prop.name), ty)?;
+                writeln!(
+                    w,
+                    r#"{:?}: {}"#,
+                    dash_to_camel(&prop.name),
+                    prop.ty.ts_type()
+                )?;
             }
             writeln!(w, r#"}}"#)?;
         }
@@ -360,3 +848,223 @@ impl BackendConfig {
         template_backend_config
     }
 }
+
+/// An entry which can be merged with the one with the same name in another layer.
+trait LayerItem {
+    fn name(&self) -> &str;
+    fn merge(&mut self, other: Self, scope: &str, conflicts: &mut Vec<String>);
+}
+
+fn merge_layer_items<T: LayerItem>(
+    base: &mut Vec<T>,
+    overlay: Vec<T>,
+    scope: &str,
+    conflicts: &mut Vec<String>,
+) {
+    for item in overlay {
+        match base.iter_mut().find(|x| x.name() == item.name()) {
+            Some(x) => x.merge(item, scope, conflicts),
+            None => base.push(item),
+        }
+    }
+}
+
+fn merge_description(base: &mut String, other: String) {
+    if !other.is_empty() {
+        *base = other;
+    }
+}
+
+fn merge_reference(base: &mut Option<Url>, other: Option<Url>) {
+    if other.is_some() {
+        *base = other;
+    }
+}
+
+impl LayerItem for ElementConfig {
+    fn name(&self) -> &str {
+        &self.tag_name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, conflicts: &mut Vec<String>) {
+        let scope = format!(" in element <{}>", self.tag_name);
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+        merge_layer_items(&mut self.attribute, other.attribute, &scope, conflicts);
+        merge_layer_items(&mut self.event, other.event, &scope, conflicts);
+        self.deprecated |= other.deprecated;
+    }
+}
+
+impl LayerItem for ComponentConfig {
+    fn name(&self) -> &str {
+        &self.tag_name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, conflicts: &mut Vec<String>) {
+        let scope = format!(" in component <{}>", self.tag_name);
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+        merge_layer_items(&mut self.property, other.property, &scope, conflicts);
+        merge_layer_items(&mut self.event, other.event, &scope, conflicts);
+        self.deprecated |= other.deprecated;
+    }
+}
+
+impl LayerItem for AttributeConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, scope: &str, conflicts: &mut Vec<String>) {
+        // `any` means the type is not specified in the overlay
+        if other.ty != PropertyType::Any {
+            if self.ty != PropertyType::Any && self.ty != other.ty {
+                conflicts.push(format!(
+                    "the type of `{}`{} is changed from `{}` to `{}`",
+                    self.name, scope, self.ty, other.ty
+                ));
+            }
+            self.ty = other.ty;
+        }
+        if other.default.is_some() {
+            self.default = other.default;
+        }
+        if other.since.is_some() {
+            self.since = other.since;
+        }
+        if other.until.is_some() {
+            self.until = other.until;
+        }
+        self.required |= other.required;
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+        let scope = format!(" of `{}`{}", self.name, scope);
+        merge_layer_items(
+            &mut self.value_option,
+            other.value_option,
+            &scope,
+            conflicts,
+        );
+        self.deprecated |= other.deprecated;
+    }
+}
+
+impl LayerItem for ValueOption {
+    fn name(&self) -> &str {
+        &self.value
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        merge_description(&mut self.description, other.description);
+        self.deprecated |= other.deprecated;
+    }
+}
+
+impl LayerItem for EventConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+        self.deprecated |= other.deprecated;
+    }
+}
+
+impl LayerItem for MediaTypeConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
+
+impl LayerItem for MediaFeatureConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, conflicts: &mut Vec<String>) {
+        if self.ty != other.ty {
+            conflicts.push(format!(
+                "the type of media feature `{}` is changed from `{:?}` to `{:?}`",
+                self.name, self.ty, other.ty
+            ));
+            self.ty = other.ty;
+        }
+        for option in other.options {
+            if !self.options.contains(&option) {
+                self.options.push(option);
+            }
+        }
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
+
+impl LayerItem for PseudoClassConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
+
+impl LayerItem for PseudoElementConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
+
+impl LayerItem for StylePropertyConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, _conflicts: &mut Vec<String>) {
+        for option in other.options {
+            if !self.options.contains(&option) {
+                self.options.push(option);
+            }
+        }
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
+
+impl LayerItem for StyleFunctionConfig {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Self, _scope: &str, conflicts: &mut Vec<String>) {
+        // parameters are replaced as a whole since they are ordered
+        if !other.parameter.is_empty() {
+            let names = |x: &[StyleFunctionParameterConfig]| {
+                x.iter().map(|x| x.name.clone()).collect::<Vec<_>>()
+            };
+            if !self.parameter.is_empty() && names(&self.parameter) != names(&other.parameter) {
+                conflicts.push(format!(
+                    "the parameters of style function `{}` are changed",
+                    self.name
+                ));
+            }
+            self.parameter = other.parameter;
+        }
+        merge_description(&mut self.description, other.description);
+        merge_reference(&mut self.reference, other.reference);
+    }
+}
@@ -1,13 +1,19 @@
 use std::{
+    collections::HashMap,
     future::Future,
     path::{Path, PathBuf},
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
 };
 
-use lsp_server::{Message, Notification};
-use lsp_types::Url;
-use tokio::sync::mpsc;
+use lsp_server::{Message, Notification, Request, RequestId, Response};
+use lsp_types::{Diagnostic, Url};
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::config::{BackendConfigLayers, ProjectConfig, SettingsLayer};
 
 pub(crate) mod backend_configuration;
 pub(crate) mod project;
@@ -25,21 +31,45 @@ pub(crate) enum FileLang {
     Wxss,
     Json,
     OtherSs,
+    Script,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ServerContextOptions {
     pub(crate) ignore_paths: Vec<PathBuf>,
     pub(crate) enable_other_ss: bool,
 }
 
+/// The LSP features which are optional for the client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientSupport {
+    pub(crate) watched_files_registration: bool,
+    pub(crate) definition_link: bool,
+    pub(crate) declaration_link: bool,
+    pub(crate) configuration: bool,
+    pub(crate) did_change_configuration_registration: bool,
+    pub(crate) relative_pattern: bool,
+    pub(crate) semantic_tokens_refresh: bool,
+    pub(crate) pull_diagnostics: bool,
+    pub(crate) diagnostic_refresh: bool,
+}
+
 #[derive(Clone)]
 pub(crate) struct ServerContext {
     sender: mpsc::WeakUnboundedSender<Message>,
-    backend_config: Arc<backend_configuration::BackendConfig>,
+    backend_config: Arc<Mutex<Arc<backend_configuration::BackendConfig>>>,
+    backend_config_layers: Arc<Mutex<BackendConfigLayers>>,
+    backend_config_diagnostics: Arc<Mutex<HashMap<PathBuf, Vec<Diagnostic>>>>,
+    client_support: Arc<ClientSupport>,
     projects: Arc<Mutex<Vec<(PathBuf, mpsc::UnboundedSender<TaskFn>)>>>,
     default_project: mpsc::UnboundedSender<TaskFn>,
-    options: Arc<ServerContextOptions>,
+    options: Arc<Mutex<Arc<ServerContextOptions>>>,
+    workspace_folders: Arc<Mutex<Vec<PathBuf>>>,
+    editor_settings: Arc<Mutex<SettingsLayer>>,
+    project_configs: Arc<Mutex<HashMap<PathBuf, Option<Arc<ProjectConfig>>>>>,
+    pending_requests: Arc<Mutex<HashMap<RequestId, oneshot::Sender<Response>>>>,
+    next_request_id: Arc<AtomicU32>,
+    diagnostics_revision: Arc<watch::Sender<u64>>,
 }
 
 impl ServerContext {
@@ -48,14 +78,26 @@ impl ServerContext {
         backend_config: backend_configuration::BackendConfig,
         initial_projects: Vec<project::Project>,
         options: ServerContextOptions,
+        client_support: ClientSupport,
+        workspace_folders: Vec<PathBuf>,
+        editor_settings: SettingsLayer,
     ) -> Self {
         let sender = sender.downgrade();
         let mut ret = Self {
             sender,
-            backend_config: Arc::new(backend_config),
+            backend_config: Arc::new(Mutex::new(Arc::new(backend_config))),
+            backend_config_layers: Arc::new(Mutex::new(Default::default())),
+            backend_config_diagnostics: Arc::new(Mutex::new(HashMap::new())),
+            client_support: Arc::new(client_support),
             projects: Arc::new(Mutex::new(vec![])),
             default_project: Self::spawn_project_thread(Default::default()),
-            options: Arc::new(options),
+            options: Arc::new(Mutex::new(Arc::new(options))),
+            workspace_folders: Arc::new(Mutex::new(workspace_folders)),
+            editor_settings: Arc::new(Mutex::new(editor_settings)),
+            project_configs: Arc::new(Mutex::new(HashMap::new())),
+            pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            next_request_id: Arc::new(AtomicU32::new(0)),
+            diagnostics_revision: Arc::new(watch::Sender::new(0)),
         };
         for proj in initial_projects {
             ret.add_project(proj);
@@ -63,8 +105,66 @@ impl ServerContext {
         ret
     }
 
-    pub(crate) fn options(&self) -> &ServerContextOptions {
-        &self.options
+    pub(crate) fn options(&self) -> Arc<ServerContextOptions> {
+        self.options.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_options(&self, options: ServerContextOptions) {
+        *self.options.lock().unwrap() = Arc::new(options);
+    }
+
+    pub(crate) fn workspace_folders(&self) -> Vec<PathBuf> {
+        self.workspace_folders.lock().unwrap().clone()
+    }
+
+    pub(crate) fn add_workspace_folder(&self, p: PathBuf) {
+        let mut folders = self.workspace_folders.lock().unwrap();
+        if !folders.contains(&p) {
+            folders.push(p);
+        }
+    }
+
+    pub(crate) fn remove_workspace_folder(&self, p: &Path) {
+        self.workspace_folders.lock().unwrap().retain(|x| x != p);
+    }
+
+    pub(crate) fn editor_settings(&self) -> SettingsLayer {
+        self.editor_settings.lock().unwrap().clone()
+    }
+
+    /// Merge `layer` into the editor settings, returning whether they changed.
+    pub(crate) fn update_editor_settings(&self, layer: SettingsLayer) -> bool {
+        let mut editor_settings = self.editor_settings.lock().unwrap();
+        let old = editor_settings.clone();
+        editor_settings.merge(layer);
+        *editor_settings != old
+    }
+
+    /// Get the nearest configuration file of `dir` .
+    ///
+    /// The result is cached until `clear_project_configs` is called.
+    pub(crate) fn project_config(&self, dir: &Path) -> Option<Arc<ProjectConfig>> {
+        let mut project_configs = self.project_configs.lock().unwrap();
+        if let Some(x) = project_configs.get(dir) {
+            return x.clone();
+        }
+        let ret = match ProjectConfig::find(dir) {
+            Ok(x) => x.map(|(_, config)| Arc::new(config)),
+            Err(err) => {
+                log::error!("{}", err);
+                None
+            }
+        };
+        project_configs.insert(dir.to_path_buf(), ret.clone());
+        ret
+    }
+
+    pub(crate) fn clear_project_configs(&self) {
+        self.project_configs.lock().unwrap().clear();
+    }
+
+    pub(crate) fn client_support(&self) -> &ClientSupport {
+        &self.client_support
     }
 
     fn spawn_project_thread(project: project::Project) -> mpsc::UnboundedSender<TaskFn> {
@@ -91,7 +191,74 @@ impl ServerContext {
     }
 
     pub(crate) fn backend_config(&self) -> Arc<backend_configuration::BackendConfig> {
-        self.backend_config.clone()
+        self.backend_config.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_backend_config(&self, backend_config: backend_configuration::BackendConfig) {
+        *self.backend_config.lock().unwrap() = Arc::new(backend_config);
+    }
+
+    /// The backend configuration layers, whose files are watched for hot-reloading.
+    pub(crate) fn backend_config_layers(&self) -> BackendConfigLayers {
+        self.backend_config_layers.lock().unwrap().clone()
+    }
+
+    /// Set the backend configuration layers, returning the old ones.
+    pub(crate) fn set_backend_config_layers(
+        &self,
+        layers: BackendConfigLayers,
+    ) -> BackendConfigLayers {
+        std::mem::replace(&mut *self.backend_config_layers.lock().unwrap(), layers)
+    }
+
+    /// The diagnostics of the backend configuration files, which are pulled by the client.
+    ///
+    /// The files are sorted by their paths.
+    pub(crate) fn backend_config_diagnostics(&self) -> Vec<(PathBuf, Vec<Diagnostic>)> {
+        let mut ret: Vec<_> = self
+            .backend_config_diagnostics
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(p, diagnostics)| (p.clone(), diagnostics.clone()))
+            .collect();
+        ret.sort_by(|a, b| a.0.cmp(&b.0));
+        ret
+    }
+
+    pub(crate) fn backend_config_file_diagnostics(&self, p: &Path) -> Option<Vec<Diagnostic>> {
+        self.backend_config_diagnostics
+            .lock()
+            .unwrap()
+            .get(p)
+            .cloned()
+    }
+
+    /// Store the diagnostics of a backend configuration file, or remove them if `None` .
+    pub(crate) fn set_backend_config_file_diagnostics(
+        &self,
+        p: &Path,
+        diagnostics: Option<Vec<Diagnostic>>,
+    ) {
+        let mut map = self.backend_config_diagnostics.lock().unwrap();
+        match diagnostics {
+            Some(x) => {
+                map.insert(p.to_path_buf(), x);
+            }
+            None => {
+                map.remove(p);
+            }
+        }
+    }
+
+    /// Mark the diagnostics as possibly changed, waking up the pending workspace diagnostic requests.
+    pub(crate) fn diagnostics_changed(&self) {
+        self.diagnostics_revision.send_modify(|x| *x += 1);
+    }
+
+    /// Watch the changes marked by `diagnostics_changed` .
+    pub(crate) fn watch_diagnostics(&self) -> watch::Receiver<u64> {
+        self.diagnostics_revision.subscribe()
     }
 
     pub(crate) fn send_notification<T: serde::Serialize>(
@@ -109,6 +276,50 @@ impl ServerContext {
         Ok(())
     }
 
+    /// Send a request to the client and wait for the response.
+    pub(crate) async fn send_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let Some(sender) = self.sender.upgrade() else {
+            return Err(anyhow::Error::msg("the connection is closed"));
+        };
+        let n = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let id = RequestId::from(format!("glassEaselAnalyzer/{}", n));
+        let (ret_sender, ret_receiver) = oneshot::channel();
+        self.pending_requests
+            .lock()
+            .unwrap()
+            .insert(id.clone(), ret_sender);
+        sender.send(Message::Request(Request {
+            id,
+            method: method.to_string(),
+            params,
+        }))?;
+        let res = ret_receiver.await?;
+        if let Some(err) = res.error {
+            return Err(anyhow::anyhow!(
+                "{} request failed: {}",
+                method,
+                err.message
+            ));
+        }
+        Ok(res.result.unwrap_or_default())
+    }
+
+    /// Pass the response to the pending request, or return it back if it is not expected.
+    pub(crate) fn handle_response(&self, res: Response) -> Option<Response> {
+        let ret_sender = self.pending_requests.lock().unwrap().remove(&res.id);
+        match ret_sender {
+            Some(ret_sender) => {
+                let _ = ret_sender.send(res);
+                None
+            }
+            None => Some(res),
+        }
+    }
+
     async fn get_project_thread_sender(
         &self,
         path: &Path,
@@ -170,6 +381,77 @@ impl ServerContext {
         .await
     }
 
+    /// Run a task on each project thread (including the default one) and collect the results.
+    pub(crate) async fn all_project_thread_tasks<R: 'static + Send>(
+        &self,
+        f: impl 'static + Send + Clone + FnOnce(&mut project::Project) -> R,
+    ) -> Vec<R> {
+        let mut senders: Vec<_> = self
+            .projects
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, sender)| sender.clone())
+            .collect();
+        senders.push(self.default_project.clone());
+        let mut receivers = vec![];
+        for sender in senders {
+            let f = f.clone();
+            let (ret_sender, ret_receiver) = tokio::sync::oneshot::channel();
+            let r = sender.send(Box::new(move |project| {
+                let r = f(project);
+                let _ = ret_sender.send(r);
+                Box::pin(async {})
+            }));
+            if r.is_ok() {
+                receivers.push(ret_receiver);
+            }
+        }
+        let mut ret = vec![];
+        for receiver in receivers {
+            if let Ok(r) = receiver.await {
+                ret.push(r);
+            }
+        }
+        ret
+    }
+
+    /// Unload the projects whose roots match `f` , and stop their threads.
+    ///
+    /// The paths of the files in the removed projects are returned.
+    pub(crate) async fn remove_projects(
+        &self,
+        f: impl Fn(&Path) -> bool,
+    ) -> Vec<(PathBuf, FileLang)> {
+        let removed: Vec<_> = {
+            let mut projects = self.projects.lock().unwrap();
+            let (removed, kept) = std::mem::take(&mut *projects)
+                .into_iter()
+                .partition(|(p, _)| f(p));
+            *projects = kept;
+            removed
+        };
+        let mut ret = vec![];
+        for (_, sender) in removed {
+            let (ret_sender, ret_receiver) = oneshot::channel();
+            let r = sender.send(Box::new(move |project| {
+                let files: Vec<_> = project
+                    .list_file_paths()
+                    .map(|(p, file_lang)| (p.to_path_buf(), file_lang))
+                    .collect();
+                let _ = ret_sender.send(files);
+                Box::pin(async {})
+            }));
+            // the thread exits after the queued tasks when the sender is dropped
+            if r.is_ok() {
+                if let Ok(files) = ret_receiver.await {
+                    ret.extend(files);
+                }
+            }
+        }
+        ret
+    }
+
     pub(crate) async fn clear_all_projects(&self) {
         let mut projects = self.projects.lock().unwrap();
         projects.clear();
@@ -6,14 +6,21 @@ use std::{
 
 use futures::StreamExt;
 use glass_easel_template_compiler::{
-    parse::{ParseError, ParseErrorKind, ParseErrorLevel, Template},
+    parse::{
+        tag::{ElementKind, Value},
+        ParseError, ParseErrorKind, ParseErrorLevel, Template,
+    },
     TmplConvertedExpr, TmplGroup,
 };
-use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range, SemanticToken};
 use tokio::sync::Mutex as AsyncMutex;
 
-use super::{FileLang, ServerContextOptions};
-use crate::wxss::{self, Location, StyleSheet};
+use super::{backend_configuration::BackendConfig, FileLang, ServerContextOptions};
+use crate::{
+    component_script::{ComponentScript, ComponentScriptInfo},
+    json_utils::JsonEntryLocation,
+    wxss::{self, Location, StyleSheet},
+};
 
 #[derive(Debug)]
 pub(crate) struct FileContentMetadata {
@@ -21,39 +28,45 @@ pub(crate) struct FileContentMetadata {
     pub(crate) file_lang: FileLang,
     pub(crate) content: String,
     pub(crate) line_starts: Vec<usize>,
+    parse_diagnostics: Vec<Diagnostic>,
 }
 
 impl FileContentMetadata {
-    fn new(content: String, file_lang: FileLang) -> Self {
+    pub(crate) fn new(content: String, file_lang: FileLang) -> Self {
+        let mut line_starts = vec![];
+        line_starts.push(0);
+        let bytes = content.as_bytes();
+        for (idx, byte) in bytes.iter().enumerate() {
+            let byte = *byte;
+            if byte == b'\n' || (byte == b'\r' && bytes.get(idx + 1) != Some(&b'\n')) {
+                line_starts.push(idx + 1);
+            }
+        }
         FileContentMetadata {
             opened: false,
             file_lang,
             content,
-            line_starts: vec![],
+            line_starts,
+            parse_diagnostics: vec![],
         }
     }
 
+    fn with_parse_diagnostics(mut self, diagnostics: &[Diagnostic]) -> Self {
+        self.parse_diagnostics = diagnostics.to_vec();
+        self
+    }
+
+    /// The diagnostics generated when the content is parsed.
+    pub(crate) fn parse_diagnostics(&self) -> &[Diagnostic] {
+        &self.parse_diagnostics
+    }
+
     fn open(&mut self) {
         self.opened = true;
-        let mut line_starts = vec![];
-        line_starts.push(0);
-        let mut iter = self.content.as_bytes().iter().enumerate();
-        while let Some((idx, byte)) = iter.next() {
-            let byte = *byte;
-            if byte == b'\n' {
-                line_starts.push(idx + 1);
-            } else if byte == b'\r' {
-                if self.content.as_bytes()[idx + 1] != b'\n' {
-                    line_starts.push(idx + 1);
-                }
-            }
-        }
-        self.line_starts = line_starts;
     }
 
     fn close(&mut self) {
         self.opened = false;
-        self.line_starts.truncate(0);
     }
 
     pub(crate) fn get_line_utf16_len(&self, line: u32) -> u32 {
@@ -92,17 +105,56 @@ impl FileContentMetadata {
             .count();
         (line as u32, utf16_col as u32)
     }
+
+    /// Get the leading spaces and tabs of the line.
+    pub(crate) fn line_indent(&self, line: u32) -> &str {
+        let Some(line_start) = self.line_starts.get(line as usize).cloned() else {
+            return "";
+        };
+        let s = &self.content[line_start..];
+        &s[..s.len() - s.trim_start_matches([' ', '\t']).len()]
+    }
 }
 
 #[derive(Debug, Clone, Default, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct JsonConfig {
     #[serde(default)]
-    #[allow(dead_code)]
     component: bool,
     #[serde(default)]
     #[allow(dead_code)]
     using_components: HashMap<String, String>,
+    #[serde(default)]
+    component_generics: HashMap<String, ComponentGenericConfig>,
+    #[serde(default)]
+    component_placeholder: HashMap<String, String>,
+    #[serde(default, alias = "subPackages")]
+    subpackages: Vec<SubpackageConfig>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubpackageConfig {
+    root: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ComponentGenericConfig {
+    Enabled(#[allow(dead_code)] bool),
+    Detailed {
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+impl ComponentGenericConfig {
+    pub(crate) fn default_component(&self) -> Option<&str> {
+        match self {
+            Self::Enabled(_) => None,
+            Self::Detailed { default } => default.as_deref(),
+        }
+    }
 }
 
 pub(crate) struct Project {
@@ -113,6 +165,12 @@ pub(crate) struct Project {
     template_group: TmplGroup,
     cached_wxml_converted_expr: HashMap<String, TmplConvertedExpr>,
     style_sheet_map: HashMap<PathBuf, StyleSheet>,
+    component_script_map: HashMap<PathBuf, ComponentScript>,
+    cached_semantic_tokens: HashMap<PathBuf, (String, Vec<SemanticToken>)>,
+    next_semantic_tokens_result_id: u64,
+    /// The diagnostics and their result ids, cleared whenever any file in the project changes.
+    cached_diagnostics: HashMap<PathBuf, (String, Vec<Diagnostic>)>,
+    cached_diagnostics_backend_config: Option<Arc<BackendConfig>>,
     enable_other_ss: bool,
 }
 
@@ -126,6 +184,11 @@ impl Default for Project {
             template_group: TmplGroup::new(),
             cached_wxml_converted_expr: HashMap::new(),
             style_sheet_map: HashMap::new(),
+            component_script_map: HashMap::new(),
+            cached_semantic_tokens: HashMap::new(),
+            next_semantic_tokens_result_id: 0,
+            cached_diagnostics: HashMap::new(),
+            cached_diagnostics_backend_config: None,
             enable_other_ss: false,
         }
     }
@@ -231,6 +294,11 @@ impl Project {
             template_group: TmplGroup::new(),
             cached_wxml_converted_expr: HashMap::new(),
             style_sheet_map: HashMap::new(),
+            component_script_map: HashMap::new(),
+            cached_semantic_tokens: HashMap::new(),
+            next_semantic_tokens_result_id: 0,
+            cached_diagnostics: HashMap::new(),
+            cached_diagnostics_backend_config: None,
             enable_other_ss: options.enable_other_ss,
         }
     }
@@ -297,6 +365,9 @@ impl Project {
                     FileLang::Json => {
                         let _ = self.cleanup_json(abs_path);
                     }
+                    FileLang::Script => {
+                        let _ = self.cleanup_component_script(abs_path);
+                    }
                     _ => {}
                 }
             }
@@ -317,6 +388,13 @@ impl Project {
                 let content = std::fs::read_to_string(abs_path)?;
                 self.update_json(abs_path, content)?;
             }
+            Some("js") | Some("ts")
+                if abs_path.with_extension("wxml").is_file()
+                    || self.component_script_map.contains_key(abs_path) =>
+            {
+                let content = std::fs::read_to_string(abs_path)?;
+                self.update_component_script(abs_path, content);
+            }
             _ => {}
         }
         Ok(())
@@ -346,7 +424,7 @@ impl Project {
                             return;
                         };
                         match ext {
-                            "wxml" | "wxss" | "json" => {}
+                            "wxml" | "wxss" | "json" | "js" | "ts" => {}
                             "css" | "less" | "scss" if enable_other_ss => {}
                             _ => {
                                 return;
@@ -366,6 +444,14 @@ impl Project {
                             "json" => {
                                 let _ = project.update_json(&abs_path, content);
                             }
+                            "js" | "ts" => {
+                                if tokio::fs::try_exists(abs_path.with_extension("wxml"))
+                                    .await
+                                    .is_ok_and(|x| x)
+                                {
+                                    project.update_component_script(&abs_path, content);
+                                }
+                            }
                             "css" | "less" | "scss" if enable_other_ss => {
                                 if tokio::fs::try_exists(abs_path.with_extension("wxml"))
                                     .await
@@ -399,6 +485,19 @@ impl Project {
         self.file_contents.get(abs_path)
     }
 
+    pub(crate) fn list_file_paths(&self) -> impl Iterator<Item = (&Path, FileLang)> {
+        self.file_contents
+            .iter()
+            .map(|(p, x)| (p.as_path(), x.file_lang))
+    }
+
+    pub(crate) fn list_opened_file_paths(&self) -> impl Iterator<Item = (&Path, FileLang)> {
+        self.file_contents
+            .iter()
+            .filter(|(_, x)| x.opened)
+            .map(|(p, x)| (p.as_path(), x.file_lang))
+    }
+
     pub(crate) fn cached_file_content(&self, abs_path: &Path) -> Option<&FileContentMetadata> {
         self.file_contents.get(abs_path)
     }
@@ -412,6 +511,7 @@ impl Project {
     }
 
     fn update_json(&mut self, abs_path: &Path, content: String) -> anyhow::Result<Vec<Diagnostic>> {
+        self.cached_diagnostics.clear();
         let mut ret = vec![];
         let json_config: Result<JsonConfig, _> = serde_json::from_str(&content);
         match json_config {
@@ -447,12 +547,13 @@ impl Project {
         }
         self.file_contents.insert(
             abs_path.to_path_buf(),
-            FileContentMetadata::new(content, FileLang::Json),
+            FileContentMetadata::new(content, FileLang::Json).with_parse_diagnostics(&ret),
         );
         Ok(ret)
     }
 
     fn cleanup_json(&mut self, abs_path: &Path) -> anyhow::Result<()> {
+        self.cached_diagnostics.clear();
         self.json_config_map.remove(abs_path);
         self.file_contents.remove(abs_path);
         Ok(())
@@ -484,13 +585,27 @@ impl Project {
         self.json_config_map.get(abs_path)
     }
 
+    pub(crate) fn find_json_entry(
+        &self,
+        abs_path: &Path,
+        key_path: &[&str],
+    ) -> Option<JsonEntryLocation> {
+        let content = self.cached_file_content(abs_path)?;
+        crate::json_utils::find_json_entry(content, key_path)
+    }
+
     fn update_wxss(
         &mut self,
         abs_path: &Path,
         content: String,
         is_other_ss: bool,
     ) -> anyhow::Result<Vec<Diagnostic>> {
+        self.cached_diagnostics.clear();
         let (ss, err_list) = StyleSheet::parse_str(abs_path, &content);
+        let diagnostics: Vec<_> = err_list
+            .into_iter()
+            .filter_map(diagnostic_from_wxss_parse_error)
+            .collect();
         self.file_contents.insert(
             abs_path.to_path_buf(),
             FileContentMetadata::new(
@@ -500,17 +615,15 @@ impl Project {
                 } else {
                     FileLang::Wxss
                 },
-            ),
+            )
+            .with_parse_diagnostics(&diagnostics),
         );
         self.style_sheet_map.insert(abs_path.to_path_buf(), ss);
-        let diagnostics = err_list
-            .into_iter()
-            .filter_map(diagnostic_from_wxss_parse_error)
-            .collect();
         Ok(diagnostics)
     }
 
     fn cleanup_wxss(&mut self, abs_path: &Path) -> anyhow::Result<()> {
+        self.cached_diagnostics.clear();
         self.json_config_map.remove(abs_path);
         self.file_contents.remove(abs_path);
         Ok(())
@@ -532,6 +645,7 @@ impl Project {
         if let Some(x) = self.file_contents.get_mut(abs_path) {
             x.close();
         }
+        self.cached_semantic_tokens.remove(abs_path);
         if std::fs::metadata(abs_path).ok().map(|x| x.is_file()) != Some(true) {
             self.cleanup_wxss(abs_path)?;
         }
@@ -576,20 +690,22 @@ impl Project {
     }
 
     fn update_wxml(&mut self, abs_path: &Path, content: String) -> anyhow::Result<Vec<Diagnostic>> {
+        self.cached_diagnostics.clear();
         let tmpl_path = self.unix_rel_path_or_fallback(&abs_path);
         let err_list = self.template_group.add_tmpl(&tmpl_path, &content);
-        self.file_contents.insert(
-            abs_path.to_path_buf(),
-            FileContentMetadata::new(content, FileLang::Wxml),
-        );
-        let diagnostics = err_list
+        let diagnostics: Vec<_> = err_list
             .into_iter()
             .filter_map(diagnostic_from_wxml_parse_error)
             .collect();
+        self.file_contents.insert(
+            abs_path.to_path_buf(),
+            FileContentMetadata::new(content, FileLang::Wxml).with_parse_diagnostics(&diagnostics),
+        );
         Ok(diagnostics)
     }
 
     fn cleanup_wxml(&mut self, abs_path: &Path) -> anyhow::Result<()> {
+        self.cached_diagnostics.clear();
         let tmpl_path = self.unix_rel_path_or_fallback(&abs_path);
         self.template_group.remove_tmpl(&tmpl_path);
         self.cached_wxml_converted_expr.remove(&tmpl_path);
@@ -613,6 +729,7 @@ impl Project {
         if let Some(x) = self.file_contents.get_mut(abs_path) {
             x.close();
         }
+        self.cached_semantic_tokens.remove(abs_path);
         if std::fs::metadata(abs_path).ok().map(|x| x.is_file()) != Some(true) {
             self.cleanup_wxml(abs_path)?;
         }
@@ -625,6 +742,68 @@ impl Project {
         Ok(tree)
     }
 
+    /// Keep the semantic tokens sent to the client for later delta requests.
+    ///
+    /// Only the latest result of each file is kept. Returns the result id.
+    pub(crate) fn cache_semantic_tokens(
+        &mut self,
+        abs_path: &Path,
+        data: Vec<SemanticToken>,
+    ) -> String {
+        self.next_semantic_tokens_result_id += 1;
+        let result_id = self.next_semantic_tokens_result_id.to_string();
+        self.cached_semantic_tokens
+            .insert(abs_path.to_path_buf(), (result_id.clone(), data));
+        result_id
+    }
+
+    pub(crate) fn cached_semantic_tokens(
+        &self,
+        abs_path: &Path,
+        result_id: &str,
+    ) -> Option<&[SemanticToken]> {
+        self.cached_semantic_tokens
+            .get(abs_path)
+            .filter(|(id, _)| id == result_id)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    /// Get the diagnostics cached with the same backend configuration, and their result id.
+    pub(crate) fn cached_diagnostics(
+        &self,
+        abs_path: &Path,
+        backend_config: &Arc<BackendConfig>,
+    ) -> Option<&(String, Vec<Diagnostic>)> {
+        let same_config = self
+            .cached_diagnostics_backend_config
+            .as_ref()
+            .is_some_and(|x| Arc::ptr_eq(x, backend_config));
+        if !same_config {
+            return None;
+        }
+        self.cached_diagnostics.get(abs_path)
+    }
+
+    /// Keep the diagnostics until any file in the project changes.
+    pub(crate) fn cache_diagnostics(
+        &mut self,
+        abs_path: &Path,
+        backend_config: &Arc<BackendConfig>,
+        result_id: String,
+        diagnostics: Vec<Diagnostic>,
+    ) {
+        let same_config = self
+            .cached_diagnostics_backend_config
+            .as_ref()
+            .is_some_and(|x| Arc::ptr_eq(x, backend_config));
+        if !same_config {
+            self.cached_diagnostics.clear();
+            self.cached_diagnostics_backend_config = Some(backend_config.clone());
+        }
+        self.cached_diagnostics
+            .insert(abs_path.to_path_buf(), (result_id, diagnostics));
+    }
+
     pub(crate) fn list_wxml_trees(&self) -> impl Iterator<Item = (&str, &Template)> {
         self.template_group.list_template_trees()
     }
@@ -670,6 +849,139 @@ impl Project {
             .and_then(|x| x.get_token_at_source_position(pos))
     }
 
+    fn update_component_script(&mut self, abs_path: &Path, content: String) {
+        self.cached_diagnostics.clear();
+        let is_ts = abs_path.extension().and_then(|x| x.to_str()) == Some("ts");
+        let content = FileContentMetadata::new(content, FileLang::Script);
+        let script = ComponentScript::parse(&content, is_ts);
+        let behaviors = script.behaviors.clone();
+        self.component_script_map
+            .insert(abs_path.to_path_buf(), script);
+        self.file_contents.insert(abs_path.to_path_buf(), content);
+        for spec in behaviors {
+            let Some(p) = self.resolve_script_module(abs_path, &spec, true) else {
+                continue;
+            };
+            if self.component_script_map.contains_key(&p) {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(&p) {
+                self.update_component_script(&p, content);
+            }
+        }
+    }
+
+    fn cleanup_component_script(&mut self, abs_path: &Path) -> anyhow::Result<()> {
+        self.cached_diagnostics.clear();
+        self.component_script_map.remove(abs_path);
+        self.file_contents.remove(abs_path);
+        Ok(())
+    }
+
+    fn resolve_script_module(
+        &self,
+        abs_path: &Path,
+        spec: &str,
+        check_fs: bool,
+    ) -> Option<PathBuf> {
+        if !spec.starts_with('.') && !spec.starts_with('/') {
+            return None;
+        }
+        let p = self.find_rel_path_for_file(abs_path, spec)?;
+        let mut candidates = vec![];
+        if let Some("js" | "ts") = p.extension().and_then(|x| x.to_str()) {
+            candidates.push(p.clone());
+        }
+        for ext in ["ts", "js"] {
+            candidates.extend(crate::utils::add_file_extension(&p, ext));
+        }
+        for ext in ["ts", "js"] {
+            candidates.push(p.join("index").with_extension(ext));
+        }
+        candidates
+            .into_iter()
+            .find(|x| self.component_script_map.contains_key(x) || (check_fs && x.is_file()))
+    }
+
+    /// Get the script information of a component, given the component path without extension.
+    ///
+    /// The `.ts` file is preferred if both `.ts` and `.js` exist.
+    pub(crate) fn get_component_script(&self, component_path: &Path) -> Option<&ComponentScript> {
+        self.find_component_script_path(component_path)
+            .and_then(|p| self.component_script_map.get(&p))
+    }
+
+    fn find_component_script_path(&self, component_path: &Path) -> Option<PathBuf> {
+        ["ts", "js"].into_iter().find_map(|ext| {
+            let p = crate::utils::add_file_extension(component_path, ext)?;
+            self.component_script_map.contains_key(&p).then_some(p)
+        })
+    }
+
+    /// Collect the script information of a component, including the behaviors it uses.
+    pub(crate) fn collect_component_script(
+        &self,
+        component_path: &Path,
+    ) -> Option<ComponentScriptInfo<'_>> {
+        fn rec<'a>(
+            project: &'a Project,
+            abs_path: PathBuf,
+            info: &mut ComponentScriptInfo<'a>,
+            visited: &mut Vec<PathBuf>,
+        ) {
+            if visited.contains(&abs_path) {
+                return;
+            }
+            let Some((abs_path, script)) = project.component_script_map.get_key_value(&abs_path)
+            else {
+                info.is_complete = false;
+                return;
+            };
+            visited.push(abs_path.clone());
+            if !script.is_complete {
+                info.is_complete = false;
+            }
+            info.properties
+                .extend(script.properties.iter().map(|x| (abs_path.as_path(), x)));
+            info.methods
+                .extend(script.methods.iter().map(|x| (abs_path.as_path(), x)));
+            info.events
+                .extend(script.events.iter().map(|x| (abs_path.as_path(), x)));
+            info.external_classes
+                .extend(script.external_classes.iter().map(|x| x.as_str()));
+            for spec in script.behaviors.iter() {
+                match project.resolve_script_module(abs_path, spec, false) {
+                    Some(p) => rec(project, p, info, visited),
+                    None => info.is_complete = false,
+                }
+            }
+        }
+        let abs_path = self.find_component_script_path(component_path)?;
+        let mut info = ComponentScriptInfo {
+            is_complete: true,
+            ..Default::default()
+        };
+        rec(self, abs_path, &mut info, &mut vec![]);
+        Some(info)
+    }
+
+    /// Collect the script information of the component or page which the file belongs to.
+    pub(crate) fn get_own_component_script(
+        &self,
+        abs_path: &Path,
+    ) -> Option<ComponentScriptInfo<'_>> {
+        self.collect_component_script(&abs_path.with_extension(""))
+    }
+
+    pub(crate) fn get_target_component_script(
+        &self,
+        abs_path: &Path,
+        tag_name: &str,
+    ) -> Option<ComponentScriptInfo<'_>> {
+        let target_path = self.get_target_component_path(abs_path, tag_name)?;
+        self.collect_component_script(&target_path)
+    }
+
     pub(crate) fn for_each_json_config(&self, mut f: impl FnMut(&Path, &JsonConfig)) {
         for (p, json_config) in self.json_config_map.iter() {
             f(p, json_config);
@@ -683,7 +995,14 @@ impl Project {
             .get_json_config(&json_path)
             .map(|x| x.using_components.keys())
             .unwrap_or_default();
-        self_using.chain(app_using).map(|x| x.as_str())
+        let self_generics = self
+            .get_json_config(&json_path)
+            .map(|x| x.component_generics.keys())
+            .unwrap_or_default();
+        self_using
+            .chain(self_generics)
+            .chain(app_using)
+            .map(|x| x.as_str())
     }
 
     pub(crate) fn get_target_component_path(
@@ -697,6 +1016,9 @@ impl Project {
         };
         if let Some(rel_path) = json_config.using_components.get(tag_name) {
             self.find_rel_path_for_file(&json_path, rel_path)
+        } else if let Some(generic) = json_config.component_generics.get(tag_name) {
+            let rel_path = generic.default_component()?;
+            self.find_rel_path_for_file(&json_path, rel_path)
         } else if let Some(p) = self.app_json_config.using_components.get(tag_name) {
             if let Some(root) = self.root() {
                 crate::utils::join_unix_rel_path(root, p, root).ok()
@@ -708,49 +1030,167 @@ impl Project {
         }
     }
 
+    pub(crate) fn iter_using_components(
+        &self,
+        abs_path: &Path,
+    ) -> impl Iterator<Item = (&str, &str)> {
+        let json_path = abs_path.with_extension("json");
+        self.get_json_config(&json_path)
+            .map(|x| x.using_components.iter())
+            .unwrap_or_default()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub(crate) fn iter_component_placeholders(
+        &self,
+        abs_path: &Path,
+    ) -> impl Iterator<Item = (&str, &str)> {
+        let json_path = abs_path.with_extension("json");
+        self.get_json_config(&json_path)
+            .map(|x| x.component_placeholder.iter())
+            .unwrap_or_default()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub(crate) fn get_component_placeholder(
+        &self,
+        abs_path: &Path,
+        tag_name: &str,
+    ) -> Option<&str> {
+        let json_path = abs_path.with_extension("json");
+        self.get_json_config(&json_path)?
+            .component_placeholder
+            .get(tag_name)
+            .map(|x| x.as_str())
+    }
+
+    pub(crate) fn is_global_using_component(&self, tag_name: &str) -> bool {
+        self.app_json_config.using_components.contains_key(tag_name)
+    }
+
+    /// Get the root of the subpackage which contains the file.
+    ///
+    /// Returns `None` if the file is in the main package.
+    pub(crate) fn subpackage_root_for_path(&self, abs_path: &Path) -> Option<&str> {
+        let root = self.root()?;
+        self.app_json_config
+            .subpackages
+            .iter()
+            .find(|x| {
+                let sub_root = x.root.trim_matches('/');
+                !sub_root.is_empty() && abs_path.starts_with(root.join(sub_root))
+            })
+            .map(|x| x.root.trim_matches('/'))
+    }
+
+    pub(crate) fn is_component(&self, abs_path: &Path) -> bool {
+        let json_path = abs_path.with_extension("json");
+        self.get_json_config(&json_path)
+            .map(|x| x.component)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn is_component_generic(&self, abs_path: &Path, tag_name: &str) -> bool {
+        let json_path = abs_path.with_extension("json");
+        self.get_json_config(&json_path)
+            .map(|x| x.component_generics.contains_key(tag_name))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn get_target_component_generics(
+        &self,
+        abs_path: &Path,
+        tag_name: &str,
+    ) -> Option<&HashMap<String, ComponentGenericConfig>> {
+        let target_path = self.get_target_component_path(abs_path, tag_name)?;
+        let target_json_path = crate::utils::add_file_extension(&target_path, "json")?;
+        let json_config = self.get_json_config(&target_json_path)?;
+        Some(&json_config.component_generics)
+    }
+
+    /// Get the named slots in the template of the target component.
+    ///
+    /// Returns `None` if the template is not found or contains a slot with a dynamic name.
+    pub(crate) fn get_target_component_slot_names(
+        &self,
+        abs_path: &Path,
+        tag_name: &str,
+    ) -> Option<Vec<&str>> {
+        let target_path = self.get_target_component_path(abs_path, tag_name)?;
+        let target_wxml_path = crate::utils::add_file_extension(&target_path, "wxml")?;
+        let template = self.get_wxml_tree(&target_wxml_path).ok()?;
+        let mut ret = vec![];
+        let mut has_dynamic = false;
+        crate::wxml_utils::for_each_slot(template, |elem| {
+            if let ElementKind::Slot { name, .. } = &elem.kind {
+                match &name.1 {
+                    Value::Static { value, .. } => {
+                        if !value.is_empty() && !ret.contains(&value.as_str()) {
+                            ret.push(value.as_str());
+                        }
+                    }
+                    _ => has_dynamic = true,
+                }
+            }
+        });
+        if has_dynamic {
+            return None;
+        }
+        Some(ret)
+    }
+
     pub(crate) fn search_component_wxml_usages(
         &self,
         abs_path: &Path,
         tag_name: &str,
-        mut f: impl FnMut(&Path, &Template, &str),
+        f: impl FnMut(&Path, &Template, &str),
     ) {
         if let Some(expected_target) = self.get_target_component_path(abs_path, &tag_name) {
-            let mut global_expected_tag_names = vec![];
-            if let Some(root) = self.root() {
-                for (expected_tag_name, p) in self.app_json_config.using_components.iter() {
-                    let Some(target) = crate::utils::join_unix_rel_path(root, p, root).ok() else {
-                        continue;
-                    };
-                    if target == expected_target {
-                        global_expected_tag_names.push(expected_tag_name.clone());
-                    }
+            self.search_component_wxml_usages_of_target(&expected_target, f);
+        }
+    }
+
+    /// Find all WXML files using the component located at `expected_target` (without extension).
+    pub(crate) fn search_component_wxml_usages_of_target(
+        &self,
+        expected_target: &Path,
+        mut f: impl FnMut(&Path, &Template, &str),
+    ) {
+        let mut global_expected_tag_names = vec![];
+        if let Some(root) = self.root() {
+            for (expected_tag_name, p) in self.app_json_config.using_components.iter() {
+                let Some(target) = crate::utils::join_unix_rel_path(root, p, root).ok() else {
+                    continue;
+                };
+                if target == expected_target {
+                    global_expected_tag_names.push(expected_tag_name.clone());
                 }
             }
-            self.for_each_json_config(|p, json_config| {
-                let source_wxml = p.with_extension("wxml");
-                for global_expected_tag_name in global_expected_tag_names.iter() {
-                    if json_config
-                        .using_components
-                        .contains_key(global_expected_tag_name)
-                    {
-                        continue;
-                    }
-                    if let Ok(template) = self.get_wxml_tree(&source_wxml) {
-                        f(&source_wxml, template, global_expected_tag_name);
-                    }
+        }
+        self.for_each_json_config(|p, json_config| {
+            let source_wxml = p.with_extension("wxml");
+            for global_expected_tag_name in global_expected_tag_names.iter() {
+                if json_config
+                    .using_components
+                    .contains_key(global_expected_tag_name)
+                {
+                    continue;
                 }
-                for (expected_tag_name, rel_path) in json_config.using_components.iter() {
-                    let Some(target) = self.find_rel_path_for_file(p, &rel_path) else {
-                        continue;
-                    };
-                    if target == expected_target {
-                        if let Ok(template) = self.get_wxml_tree(&source_wxml) {
-                            f(&source_wxml, template, &expected_tag_name);
-                        }
+                if let Ok(template) = self.get_wxml_tree(&source_wxml) {
+                    f(&source_wxml, template, global_expected_tag_name);
+                }
+            }
+            for (expected_tag_name, rel_path) in json_config.using_components.iter() {
+                let Some(target) = self.find_rel_path_for_file(p, &rel_path) else {
+                    continue;
+                };
+                if target == expected_target {
+                    if let Ok(template) = self.get_wxml_tree(&source_wxml) {
+                        f(&source_wxml, template, &expected_tag_name);
                     }
                 }
-            });
-        }
+            }
+        });
     }
 
     pub(crate) fn get_wxml_template_names(&self, abs_path: &Path) -> Option<Vec<String>> {
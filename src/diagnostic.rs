@@ -0,0 +1,258 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+    sync::Arc,
+};
+
+use lsp_types::{
+    Diagnostic, DocumentDiagnosticParams, DocumentDiagnosticReport, DocumentDiagnosticReportResult,
+    FullDocumentDiagnosticReport, RelatedFullDocumentDiagnosticReport,
+    RelatedUnchangedDocumentDiagnosticReport, UnchangedDocumentDiagnosticReport, Url,
+    WorkspaceDiagnosticParams, WorkspaceDiagnosticReport, WorkspaceDiagnosticReportResult,
+    WorkspaceDocumentDiagnosticReport, WorkspaceFullDocumentDiagnosticReport,
+    WorkspaceUnchangedDocumentDiagnosticReport,
+};
+
+use crate::{
+    context::{project::Project, FileLang},
+    BackendConfig, ServerContext,
+};
+
+/// Collect the parsing diagnostics and the checking results of a file.
+pub(crate) fn collect_file_diagnostics(
+    project: &Project,
+    backend_config: &BackendConfig,
+    abs_path: &Path,
+) -> Vec<Diagnostic> {
+    let Some(content) = project.cached_file_content(abs_path) else {
+        return vec![];
+    };
+    let mut diagnostics = content.parse_diagnostics().to_vec();
+    diagnostics.append(&mut crate::checker::check_file(
+        project,
+        backend_config,
+        abs_path,
+    ));
+    diagnostics
+}
+
+/// Get the diagnostics of a file and their result id.
+///
+/// The result is cached in the project until any file or the backend configuration changes.
+fn cached_file_diagnostics<'a>(
+    project: &'a mut Project,
+    backend_config: &Arc<BackendConfig>,
+    abs_path: &Path,
+) -> &'a (String, Vec<Diagnostic>) {
+    if project
+        .cached_diagnostics(abs_path, backend_config)
+        .is_none()
+    {
+        let diagnostics = collect_file_diagnostics(project, backend_config, abs_path);
+        let result_id = diagnostics_result_id(&diagnostics);
+        project.cache_diagnostics(abs_path, backend_config, result_id, diagnostics);
+    }
+    project
+        .cached_diagnostics(abs_path, backend_config)
+        .unwrap()
+}
+
+/// Generate the result id of the diagnostics.
+///
+/// The diagnostics depend on other files and the backend configuration,
+/// so the id is derived from the diagnostics themselves instead of the file version.
+fn diagnostics_result_id(diagnostics: &[Diagnostic]) -> String {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(diagnostics)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+pub(crate) async fn document_diagnostic(
+    ctx: ServerContext,
+    params: DocumentDiagnosticParams,
+) -> anyhow::Result<DocumentDiagnosticReportResult> {
+    let previous_result_id = params.previous_result_id;
+    let backend_config_file_diagnostics = params
+        .text_document
+        .uri
+        .to_file_path()
+        .ok()
+        .and_then(|p| ctx.backend_config_file_diagnostics(&p));
+    if let Some(diagnostics) = backend_config_file_diagnostics {
+        let result_id = diagnostics_result_id(&diagnostics);
+        let report = document_diagnostic_report(previous_result_id, &result_id, &diagnostics);
+        return Ok(DocumentDiagnosticReportResult::Report(report));
+    }
+    let backend_config = ctx.backend_config();
+    let report = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, _| -> DocumentDiagnosticReport {
+                let (result_id, diagnostics) =
+                    cached_file_diagnostics(project, &backend_config, &abs_path);
+                document_diagnostic_report(previous_result_id, result_id, diagnostics)
+            },
+        )
+        .await?;
+    Ok(DocumentDiagnosticReportResult::Report(report))
+}
+
+fn document_diagnostic_report(
+    previous_result_id: Option<String>,
+    result_id: &str,
+    diagnostics: &[Diagnostic],
+) -> DocumentDiagnosticReport {
+    let result_id = result_id.to_string();
+    if previous_result_id.as_ref() == Some(&result_id) {
+        DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+            related_documents: None,
+            unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport { result_id },
+        })
+    } else {
+        DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                result_id: Some(result_id),
+                items: diagnostics.to_vec(),
+            },
+        })
+    }
+}
+
+/// Report the diagnostics of all files in the workspace.
+///
+/// If the client has got the previous reports and nothing changed since then,
+/// the request is held until any change, so that the client does not need to poll repeatedly.
+pub(crate) async fn workspace_diagnostic(
+    ctx: ServerContext,
+    params: WorkspaceDiagnosticParams,
+) -> anyhow::Result<WorkspaceDiagnosticReportResult> {
+    let previous_result_ids: Arc<HashMap<Url, String>> = Arc::new(
+        params
+            .previous_result_ids
+            .into_iter()
+            .map(|x| (x.uri, x.value))
+            .collect(),
+    );
+    let mut changed = ctx.watch_diagnostics();
+    loop {
+        // mark the current revision as seen, so that the changes during collecting are not missed
+        changed.borrow_and_update();
+        let items = collect_workspace_diagnostics(&ctx, previous_result_ids.clone()).await;
+        // only hold the request when the client has got all the current reports
+        let hold = !previous_result_ids.is_empty()
+            && !items.is_empty()
+            && items
+                .iter()
+                .all(|x| matches!(x, WorkspaceDocumentDiagnosticReport::Unchanged(_)));
+        if !hold || changed.changed().await.is_err() {
+            return Ok(WorkspaceDiagnosticReportResult::Report(
+                WorkspaceDiagnosticReport { items },
+            ));
+        }
+    }
+}
+
+async fn collect_workspace_diagnostics(
+    ctx: &ServerContext,
+    previous_result_ids: Arc<HashMap<Url, String>>,
+) -> Vec<WorkspaceDocumentDiagnosticReport> {
+    let backend_config = ctx.backend_config();
+    let previous_result_ids2 = previous_result_ids.clone();
+    let files = ctx
+        .all_project_thread_tasks(move |project| {
+            let mut files: Vec<_> = project
+                .list_file_paths()
+                .filter(|(_, file_lang)| {
+                    matches!(
+                        file_lang,
+                        FileLang::Wxml | FileLang::Wxss | FileLang::Json | FileLang::OtherSs
+                    )
+                })
+                .map(|(p, _)| p.to_path_buf())
+                .collect();
+            files.sort();
+            files
+                .into_iter()
+                .filter_map(|abs_path| {
+                    let uri = Url::from_file_path(&abs_path).ok()?;
+                    let (result_id, diagnostics) =
+                        cached_file_diagnostics(project, &backend_config, &abs_path);
+                    let report = document_diagnostic_report(
+                        previous_result_ids2.get(&uri).cloned(),
+                        result_id,
+                        diagnostics,
+                    );
+                    Some(workspace_diagnostic_report(uri, report))
+                })
+                .collect::<Vec<_>>()
+        })
+        .await;
+    let mut items: Vec<_> = files.into_iter().flatten().collect();
+
+    // the backend configuration files
+    for (p, diagnostics) in ctx.backend_config_diagnostics() {
+        let Ok(uri) = Url::from_file_path(&p) else {
+            continue;
+        };
+        let result_id = diagnostics_result_id(&diagnostics);
+        let report = document_diagnostic_report(
+            previous_result_ids.get(&uri).cloned(),
+            &result_id,
+            &diagnostics,
+        );
+        items.push(workspace_diagnostic_report(uri, report));
+    }
+
+    // the files reported before but no longer exist
+    let mut removed: Vec<_> = previous_result_ids
+        .keys()
+        .filter(|uri| {
+            !items.iter().any(|x| match x {
+                WorkspaceDocumentDiagnosticReport::Full(x) => &x.uri == *uri,
+                WorkspaceDocumentDiagnosticReport::Unchanged(x) => &x.uri == *uri,
+            })
+        })
+        .cloned()
+        .collect();
+    removed.sort();
+    for uri in removed {
+        items.push(WorkspaceDocumentDiagnosticReport::Full(
+            WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: None,
+                    items: vec![],
+                },
+            },
+        ));
+    }
+    items
+}
+
+fn workspace_diagnostic_report(
+    uri: Url,
+    report: DocumentDiagnosticReport,
+) -> WorkspaceDocumentDiagnosticReport {
+    match report {
+        DocumentDiagnosticReport::Unchanged(x) => WorkspaceDocumentDiagnosticReport::Unchanged(
+            WorkspaceUnchangedDocumentDiagnosticReport {
+                uri,
+                version: None,
+                unchanged_document_diagnostic_report: x.unchanged_document_diagnostic_report,
+            },
+        ),
+        DocumentDiagnosticReport::Full(x) => {
+            WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: x.full_document_diagnostic_report,
+            })
+        }
+    }
+}
@@ -9,7 +9,7 @@ use lsp_types::{
 use serde::Deserialize;
 
 use crate::{
-    context::{project::Project, FileLang},
+    context::{backend_configuration::BackendConfig, project::Project, FileLang},
     utils::log_if_err,
     ServerContext,
 };
@@ -83,12 +83,39 @@ fn update_inline_wxs_list(ctx: &ServerContext, project: &Project, uri: &Url, abs
     }
 }
 
+/// Publish the diagnostics of a file.
+///
+/// If the client pulls the diagnostics itself, the pending workspace diagnostic requests are woken up instead.
+fn publish_file_diagnostics(
+    ctx: &ServerContext,
+    project: &Project,
+    backend_config: &BackendConfig,
+    uri: Url,
+    abs_path: &Path,
+) {
+    if ctx.client_support().pull_diagnostics {
+        ctx.diagnostics_changed();
+        return;
+    }
+    let diagnostics =
+        crate::diagnostic::collect_file_diagnostics(project, backend_config, abs_path);
+    log_if_err(ctx.send_notification(
+        "textDocument/publishDiagnostics",
+        PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        },
+    ));
+}
+
 pub(crate) async fn did_open(
     ctx: ServerContext,
     params: DidOpenTextDocumentParams,
 ) -> anyhow::Result<()> {
     log::debug!("File opened: {}", params.text_document.uri.as_str());
     let uri = params.text_document.uri.clone();
+    let backend_config = ctx.backend_config();
     log_if_err(
         ctx.clone()
             .project_thread_task(&params.text_document.uri, move |project, abs_path, _| {
@@ -106,15 +133,8 @@ pub(crate) async fn did_open(
                     _ => return,
                 };
                 match diag {
-                    Ok(diagnostics) => {
-                        log_if_err(ctx.send_notification(
-                            "textDocument/publishDiagnostics",
-                            PublishDiagnosticsParams {
-                                uri,
-                                diagnostics,
-                                version: None,
-                            },
-                        ));
+                    Ok(_) => {
+                        publish_file_diagnostics(&ctx, project, &backend_config, uri, &abs_path);
                     }
                     Err(err) => {
                         log::error!("{}", err);
@@ -132,6 +152,7 @@ pub(crate) async fn did_change(
 ) -> anyhow::Result<()> {
     log::debug!("File changed: {}", params.text_document.uri.as_str());
     let uri = params.text_document.uri.clone();
+    let backend_config = ctx.backend_config();
     log_if_err(
         ctx.clone()
             .project_thread_task(
@@ -154,15 +175,14 @@ pub(crate) async fn did_change(
                             _ => return,
                         };
                         match diag {
-                            Ok(diagnostics) => {
-                                log_if_err(ctx.send_notification(
-                                    "textDocument/publishDiagnostics",
-                                    PublishDiagnosticsParams {
-                                        uri,
-                                        diagnostics,
-                                        version: None,
-                                    },
-                                ));
+                            Ok(_) => {
+                                publish_file_diagnostics(
+                                    &ctx,
+                                    project,
+                                    &backend_config,
+                                    uri,
+                                    &abs_path,
+                                );
                             }
                             Err(err) => {
                                 log::error!("{}", err);
@@ -182,53 +202,122 @@ pub(crate) struct RequestDiagnosticsParams {
     text_document_uri: Url,
 }
 
+/// Re-check an opened file and publish the diagnostics.
+///
+/// Returns `false` if the file is not opened.
+fn refresh_diagnostics(
+    ctx: &ServerContext,
+    project: &mut Project,
+    backend_config: &BackendConfig,
+    uri: Url,
+    abs_path: &Path,
+    file_lang: FileLang,
+) -> bool {
+    let Some(content) = project.cached_file_content_if_opened(abs_path) else {
+        return false;
+    };
+    let content = content.content.clone();
+    let diag = match file_lang {
+        FileLang::Wxml => project.open_wxml(abs_path, content),
+        FileLang::Wxss => project.open_wxss(abs_path, content),
+        FileLang::Json => project.open_json(abs_path, content),
+        FileLang::OtherSs => project.open_other_ss(abs_path, content),
+        _ => return false,
+    };
+    match diag {
+        Ok(_) => {
+            publish_file_diagnostics(ctx, project, backend_config, uri, abs_path);
+        }
+        Err(err) => {
+            log::error!("{}", err);
+        }
+    }
+    true
+}
+
 pub(crate) async fn request_diagnostics(
     ctx: ServerContext,
     params: RequestDiagnosticsParams,
 ) -> anyhow::Result<bool> {
     let uri = params.text_document_uri.clone();
-    ctx.clone()
+    let backend_config = ctx.backend_config();
+    let ctx2 = ctx.clone();
+    let ret = ctx
         .project_thread_task(
             &params.text_document_uri,
             move |project, abs_path, file_lang| {
-                if let Some(content) = project.cached_file_content_if_opened(&abs_path) {
-                    let content = content.content.clone();
-                    let diag = match file_lang {
-                        FileLang::Wxml => project.open_wxml(&abs_path, content),
-                        FileLang::Wxss => project.open_wxss(&abs_path, content),
-                        FileLang::Json => project.open_json(&abs_path, content),
-                        FileLang::OtherSs => project.open_other_ss(&abs_path, content),
-                        _ => return false,
-                    };
-                    match diag {
-                        Ok(diagnostics) => {
-                            log_if_err(ctx.send_notification(
-                                "textDocument/publishDiagnostics",
-                                PublishDiagnosticsParams {
-                                    uri,
-                                    diagnostics,
-                                    version: None,
-                                },
-                            ));
-                        }
-                        Err(err) => {
-                            log::error!("{}", err);
-                        }
-                    }
-                    true
-                } else {
-                    false
-                }
+                refresh_diagnostics(&ctx2, project, &backend_config, uri, &abs_path, file_lang)
             },
         )
-        .await
+        .await?;
+    // the client may merge diagnostics from other sources, so ask it to pull again
+    if ret && ctx.client_support().pull_diagnostics {
+        request_diagnostic_refresh(&ctx);
+    }
+    Ok(ret)
+}
+
+/// Ask the client to pull the diagnostics again, if it is supported.
+pub(crate) fn request_diagnostic_refresh(ctx: &ServerContext) {
+    if !ctx.client_support().diagnostic_refresh {
+        return;
+    }
+    // the response can only be received after the current message handled
+    let ctx = ctx.clone();
+    tokio::task::spawn_local(async move {
+        if let Err(err) = ctx
+            .send_request("workspace/diagnostic/refresh", serde_json::Value::Null)
+            .await
+        {
+            log::error!("{}", err);
+        }
+    });
+}
+
+/// Re-check all opened files and publish the diagnostics.
+///
+/// If the client pulls the diagnostics, it is asked to pull them again instead.
+pub(crate) async fn republish_all_diagnostics(ctx: &ServerContext) {
+    if ctx.client_support().pull_diagnostics {
+        ctx.diagnostics_changed();
+        request_diagnostic_refresh(ctx);
+        return;
+    }
+    let backend_config = ctx.backend_config();
+    let ctx2 = ctx.clone();
+    ctx.all_project_thread_tasks(move |project| {
+        let files: Vec<_> = project
+            .list_opened_file_paths()
+            .map(|(p, file_lang)| (p.to_path_buf(), file_lang))
+            .collect();
+        for (abs_path, file_lang) in files {
+            let Ok(uri) = Url::from_file_path(&abs_path) else {
+                continue;
+            };
+            refresh_diagnostics(&ctx2, project, &backend_config, uri, &abs_path, file_lang);
+        }
+    })
+    .await;
+}
+
+fn is_backend_config_file(ctx: &ServerContext, uri: &Url) -> bool {
+    let Ok(p) = uri.to_file_path() else {
+        return false;
+    };
+    ctx.backend_config_layers().paths().contains(&p.as_path())
 }
 
 pub(crate) async fn did_save(
-    _ctx: ServerContext,
+    ctx: ServerContext,
     params: DidSaveTextDocumentParams,
 ) -> anyhow::Result<()> {
     log::debug!("File saved: {}", params.text_document.uri.as_str());
+    // for clients which cannot watch files
+    if !ctx.client_support().watched_files_registration
+        && is_backend_config_file(&ctx, &params.text_document.uri)
+    {
+        crate::config::reload_backend_config(&ctx).await;
+    }
     Ok(())
 }
 
@@ -237,25 +326,33 @@ pub(crate) async fn did_close(
     params: DidCloseTextDocumentParams,
 ) -> anyhow::Result<()> {
     log::debug!("File closed: {}", params.text_document.uri.as_str());
+    let reload_from_fs = !ctx.client_support().watched_files_registration;
     log_if_err(
         ctx.clone()
             .project_thread_task(
                 &params.text_document.uri,
-                move |project, abs_path, file_lang| match file_lang {
-                    FileLang::Json => {
-                        log_if_err(project.close_json(&abs_path));
-                    }
-                    FileLang::Wxml => {
-                        log_if_err(project.close_wxml(&abs_path));
+                move |project, abs_path, file_lang| {
+                    match file_lang {
+                        FileLang::Json => {
+                            log_if_err(project.close_json(&abs_path));
+                        }
+                        FileLang::Wxml => {
+                            log_if_err(project.close_wxml(&abs_path));
+                        }
+                        FileLang::Wxss => {
+                            log_if_err(project.close_wxss(&abs_path));
+                        }
+                        _ => {}
                     }
-                    FileLang::Wxss => {
-                        log_if_err(project.close_wxss(&abs_path));
+                    // without file watching, unsaved changes are only discarded here
+                    if reload_from_fs {
+                        project.file_created_or_changed(&abs_path);
                     }
-                    _ => {}
                 },
             )
             .await,
     );
+    ctx.diagnostics_changed();
     Ok(())
 }
 
@@ -263,7 +360,19 @@ pub(crate) async fn did_change_watched_files(
     ctx: ServerContext,
     params: DidChangeWatchedFilesParams,
 ) -> anyhow::Result<()> {
+    let mut config_changed = false;
+    let mut backend_config_changed = false;
     for change in params.changes {
+        if is_backend_config_file(&ctx, &change.uri) {
+            backend_config_changed = true;
+            continue;
+        }
+        let is_config_file = change.uri.path_segments().and_then(|mut x| x.next_back())
+            == Some(crate::config::CONFIG_FILE_NAME);
+        if is_config_file {
+            config_changed = true;
+            continue;
+        }
         match change.typ {
             FileChangeType::CREATED | FileChangeType::CHANGED => {
                 log_if_err(
@@ -285,6 +394,12 @@ pub(crate) async fn did_change_watched_files(
             }
             _ => {}
         }
+        ctx.diagnostics_changed();
+    }
+    if config_changed {
+        crate::config::reload_settings(ctx).await;
+    } else if backend_config_changed {
+        crate::config::reload_backend_config(&ctx).await;
     }
     Ok(())
 }
@@ -293,21 +408,68 @@ pub(crate) async fn did_change_workspace_folders(
     mut ctx: ServerContext,
     params: DidChangeWorkspaceFoldersParams,
 ) -> anyhow::Result<()> {
+    for folder in params.event.removed.iter() {
+        let p = lsp_types::Url::to_file_path(&folder.uri)
+            .unwrap_or_else(|_| crate::utils::generate_non_fs_fake_path(&folder.uri));
+        ctx.remove_workspace_folder(&p);
+        unload_workspace_folder(&ctx, &p).await;
+    }
     for folder in params.event.added.iter() {
         let p = lsp_types::Url::to_file_path(&folder.uri)
             .unwrap_or_else(|_| crate::utils::generate_non_fs_fake_path(&folder.uri));
-        let found_projects = Project::search_projects(&p, ctx.options()).await;
-        for mut project in found_projects {
-            project.init().await;
-            if let Some(path) = project.root().and_then(|x| x.to_str()) {
-                ctx.send_notification(
-                    "glassEaselAnalyzer/discoveredProject",
-                    crate::ProjectInfo { path },
-                )
-                .unwrap();
-            }
-            ctx.add_project(project);
-        }
+        ctx.add_workspace_folder(p.clone());
+        load_workspace_folder(&mut ctx, &p).await;
     }
     Ok(())
 }
+
+pub(crate) async fn load_workspace_folder(ctx: &mut ServerContext, p: &Path) {
+    let found_projects = Project::search_projects(p, &ctx.options()).await;
+    for mut project in found_projects {
+        project.init().await;
+        if let Some(path) = project.root().and_then(|x| x.to_str()) {
+            log_if_err(ctx.send_notification(
+                "glassEaselAnalyzer/discoveredProject",
+                crate::ProjectInfo { path },
+            ));
+        }
+        ctx.add_project(project);
+    }
+    ctx.diagnostics_changed();
+}
+
+/// Unload the projects in a removed workspace folder and clear their diagnostics.
+///
+/// The projects also in other workspace folders are kept.
+async fn unload_workspace_folder(ctx: &ServerContext, p: &Path) {
+    let workspace_folders = ctx.workspace_folders();
+    let files = ctx
+        .remove_projects(|root| {
+            root.starts_with(p) && !workspace_folders.iter().any(|x| root.starts_with(x))
+        })
+        .await;
+    if ctx.client_support().pull_diagnostics {
+        ctx.diagnostics_changed();
+        request_diagnostic_refresh(ctx);
+        return;
+    }
+    for (abs_path, file_lang) in files {
+        if !matches!(
+            file_lang,
+            FileLang::Wxml | FileLang::Wxss | FileLang::Json | FileLang::OtherSs
+        ) {
+            continue;
+        }
+        let Ok(uri) = Url::from_file_path(&abs_path) else {
+            continue;
+        };
+        log_if_err(ctx.send_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri,
+                diagnostics: vec![],
+                version: None,
+            },
+        ));
+    }
+}
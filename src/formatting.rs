@@ -1,47 +1,555 @@
-use glass_easel_template_compiler::stringify::{Stringifier, StringifyOptions};
-use lsp_types::{DocumentFormattingParams, TextEdit};
+use std::{ops::Range, path::Path};
 
-use crate::{context::FileLang, ServerContext};
+use glass_easel_template_compiler::{
+    parse::{
+        tag::{Node, TagLocation},
+        ParseErrorLevel, Position, Template, TemplateStructure,
+    },
+    stringify::{Stringifier, StringifyOptions},
+};
+use lsp_types::{
+    DocumentFormattingParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
+    FormattingOptions, TextEdit, Url,
+};
+
+use crate::{
+    config::FormatConfig,
+    context::{
+        project::{FileContentMetadata, Project},
+        FileLang,
+    },
+    utils::lsp_range_to_location,
+    wxml_utils::for_each_template_element,
+    wxss::{
+        stringify::{stringify_style_sheet, stringify_style_sheet_range},
+        StyleSheet,
+    },
+    ServerContext,
+};
+
+/// The max size of the line-based diff table.
+///
+/// Larger changes are replaced as a whole.
+const MAX_DIFF_TABLE_SIZE: usize = 4 * 1024 * 1024;
 
 pub(crate) async fn formatting(
     ctx: ServerContext,
     params: DocumentFormattingParams,
 ) -> anyhow::Result<Vec<TextEdit>> {
+    let config = format_config(&ctx, &params.text_document.uri, &params.options);
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Vec<TextEdit>> {
+                let ret = match format_file(project, &abs_path, file_lang, &config)? {
+                    Some((old, new)) => diff_text_edits(&old, &new),
+                    None => vec![],
+                };
+                Ok(ret)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+pub(crate) async fn range_formatting(
+    ctx: ServerContext,
+    params: DocumentRangeFormattingParams,
+) -> anyhow::Result<Vec<TextEdit>> {
+    let selection = lsp_range_to_location(&params.range);
+    let config = format_config(&ctx, &params.text_document.uri, &params.options);
     let ret = ctx
         .clone()
         .project_thread_task(
             &params.text_document.uri,
             move |project, abs_path, file_lang| -> anyhow::Result<Vec<TextEdit>> {
-                let ret = match file_lang {
+                let Some(content) = project.cached_file_content(&abs_path) else {
+                    return Ok(vec![]);
+                };
+                let parts = match file_lang {
                     FileLang::Wxml => {
                         let template = project.get_wxml_tree(&abs_path)?;
-                        let options = StringifyOptions {
-                            tab_size: params.options.tab_size,
-                            use_tab_character: !params.options.insert_spaces,
-                            ..Default::default()
-                        };
-                        let mut out = String::new();
-                        Stringifier::new(&mut out, "", None, options).run(template)?;
-                        let text_edit = TextEdit {
-                            range: lsp_types::Range {
-                                start: lsp_types::Position {
-                                    line: 0,
-                                    character: 0,
-                                },
-                                end: lsp_types::Position {
-                                    line: u32::MAX,
-                                    character: u32::MAX,
-                                },
-                            },
-                            new_text: out,
-                        };
-                        vec![text_edit]
+                        let options = config.stringify_options();
+                        format_wxml_range(template, content, options, &selection)?
+                    }
+                    FileLang::Wxss => {
+                        let (sheet, warnings) = StyleSheet::parse_str(&abs_path, &content.content);
+                        if warnings.iter().any(|x| x.level() >= ParseErrorLevel::Error) {
+                            return Ok(vec![]);
+                        }
+                        let options = config.wxss_stringify_options();
+                        stringify_style_sheet_range(&sheet, content, &options, &selection)
+                            .into_iter()
+                            .collect()
                     }
                     _ => vec![],
                 };
+                // the edits are limited to the formatted parts, so they never exceed the selection
+                let ret = parts
+                    .into_iter()
+                    .flat_map(|(loc, new)| {
+                        let start = content
+                            .content_index_for_line_utf16_col(loc.start.line, loc.start.utf16_col);
+                        let end = content
+                            .content_index_for_line_utf16_col(loc.end.line, loc.end.utf16_col);
+                        diff_text_edits(&content.content[start..end], &new)
+                            .into_iter()
+                            .map(move |x| TextEdit {
+                                range: lsp_types::Range {
+                                    start: offset_lsp_position(x.range.start, loc.start),
+                                    end: offset_lsp_position(x.range.end, loc.start),
+                                },
+                                new_text: x.new_text,
+                            })
+                    })
+                    .collect();
                 Ok(ret)
             },
         )
         .await??;
     Ok(ret)
 }
+
+pub(crate) async fn on_type_formatting(
+    ctx: ServerContext,
+    params: DocumentOnTypeFormattingParams,
+) -> anyhow::Result<Option<Vec<TextEdit>>> {
+    let uri = params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
+    let config = format_config(&ctx, &uri, &params.options);
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Option<Vec<TextEdit>>> {
+                if file_lang != FileLang::Wxml {
+                    return Ok(None);
+                }
+                let Some(content) = project.cached_file_content(&abs_path) else {
+                    return Ok(None);
+                };
+                let template = project.get_wxml_tree(&abs_path)?;
+                let edit =
+                    reindent_wxml_line(template, content, position.line, &params.ch, &config);
+                Ok(edit.map(|x| vec![x]))
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+/// Use the `[format]` section of the nearest configuration file.
+///
+/// The client options are used for the indentation if the configuration file does not specify it.
+fn format_config(ctx: &ServerContext, uri: &Url, options: &FormattingOptions) -> FormatConfig {
+    let mut config = FormatConfig {
+        tab_size: Some(options.tab_size),
+        use_tabs: Some(!options.insert_spaces),
+        ..Default::default()
+    };
+    let project_config = uri
+        .to_file_path()
+        .ok()
+        .and_then(|p| ctx.project_config(p.parent()?));
+    if let Some(x) = project_config {
+        config.merge(&x.format);
+    }
+    config
+}
+
+/// Format the file and returns the original content and the formatted content.
+fn format_file(
+    project: &Project,
+    abs_path: &Path,
+    file_lang: FileLang,
+    config: &FormatConfig,
+) -> anyhow::Result<Option<(String, String)>> {
+    let Some(content) = project.cached_file_content(abs_path) else {
+        return Ok(None);
+    };
+    let out = match file_lang {
+        FileLang::Wxml => {
+            let template = project.get_wxml_tree(abs_path)?;
+            format_wxml(template, config.stringify_options())?
+        }
+        FileLang::Wxss => {
+            // the source is required for verbatim parts, so parse it again for the errors
+            let (sheet, warnings) = StyleSheet::parse_str(abs_path, &content.content);
+            if warnings.iter().any(|x| x.level() >= ParseErrorLevel::Error) {
+                return Ok(None);
+            }
+            stringify_style_sheet(&sheet, content, &config.wxss_stringify_options())
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some((content.content.clone(), out)))
+}
+
+pub(crate) fn format_wxml(
+    template: &Template,
+    options: StringifyOptions,
+) -> anyhow::Result<String> {
+    let mut out = String::new();
+    Stringifier::new(&mut out, "", None, options).run(template)?;
+    Ok(out)
+}
+
+/// Format the nodes covered by `selection` , keeping the indentation of the line they start.
+///
+/// The nodes are searched in the innermost element containing `selection` .
+/// Returns the location of each node and its formatted content.
+fn format_wxml_range(
+    template: &Template,
+    content: &FileContentMetadata,
+    options: StringifyOptions,
+    selection: &Range<Position>,
+) -> anyhow::Result<Vec<(Range<Position>, String)>> {
+    // format the whole template, and find the output of each node with the source map
+    let src = &content.content;
+    let mut out = String::new();
+    let mut stringifier = Stringifier::new(
+        &mut out,
+        "",
+        Some(src),
+        StringifyOptions {
+            source_map: true,
+            ..options
+        },
+    );
+    stringifier.run(template)?;
+    let (_, Some(source_map)) = stringifier.finish() else {
+        return Ok(vec![]);
+    };
+    let out = FileContentMetadata::new(out, FileLang::Wxml);
+    let tokens: Vec<(Position, usize)> = source_map
+        .tokens()
+        .map(|x| {
+            let src_pos = Position {
+                line: x.get_src_line(),
+                utf16_col: x.get_src_col(),
+            };
+            let dest_index =
+                out.content_index_for_line_utf16_col(x.get_dst_line(), x.get_dst_col());
+            (src_pos, dest_index)
+        })
+        .collect();
+
+    // find the covered nodes
+    let mut covered: Vec<Range<Position>> = vec![];
+    let mut items: Vec<(Range<Position>, Vec<&Node>)> = vec![];
+    items.extend(
+        template
+            .globals
+            .imports
+            .iter()
+            .map(|x| (tag_location_range(&x.tag_location), vec![])),
+    );
+    items.extend(
+        template
+            .globals
+            .scripts
+            .iter()
+            .map(|x| (tag_location_range(&x.tag_location()), vec![])),
+    );
+    items.extend(template.globals.sub_templates.iter().map(|x| {
+        (
+            tag_location_range(&x.tag_location),
+            x.content.iter().collect(),
+        )
+    }));
+    items.extend(template.content.iter().map(node_item));
+    while covered.is_empty() {
+        let mut containing = None;
+        for (loc, children) in items {
+            if selection.start <= loc.start && loc.end <= selection.end {
+                covered.push(loc);
+            } else if loc.start <= selection.start && selection.end <= loc.end {
+                containing = Some(children);
+            }
+        }
+        let Some(children) = containing else {
+            break;
+        };
+        items = children.into_iter().map(node_item).collect();
+    }
+
+    let mut ret = vec![];
+    for loc in covered {
+        // the output of the node starts from its first token and ends before any other token
+        let in_node = |pos: &Position| loc.start <= *pos && *pos < loc.end;
+        let node_tokens = || tokens.iter().filter(|(pos, _)| in_node(pos));
+        let Some(first_token) = node_tokens().map(|(_, index)| *index).min() else {
+            continue;
+        };
+        // some leading parts like `<!` are written without tokens
+        let (out_line, _) = out.line_utf16_col_for_content_index(first_token);
+        let line_content_start =
+            out.line_starts[out_line as usize] + out.line_indent(out_line).len();
+        let out_start = if tokens
+            .iter()
+            .any(|(_, index)| (line_content_start..first_token).contains(index))
+        {
+            first_token
+        } else {
+            line_content_start
+        };
+        let out_end = tokens
+            .iter()
+            .filter(|(pos, index)| *index > out_start && !in_node(pos))
+            .map(|(_, index)| *index)
+            .min()
+            .unwrap_or(out.content.len());
+        // the output may be mixed with other nodes, e.g. a `<block wx:for>` merged into its child
+        if node_tokens().any(|(_, index)| *index >= out_end) {
+            continue;
+        }
+        let formatted = out.content[out_start..out_end].trim_end();
+
+        // text nodes contain the surrounding whitespace
+        let start = content.content_index_for_line_utf16_col(loc.start.line, loc.start.utf16_col);
+        let end = content.content_index_for_line_utf16_col(loc.end.line, loc.end.utf16_col);
+        let old = &src[start..end];
+        let old_start = start + (old.len() - old.trim_start().len());
+        let old_end = start + old.trim_end().len();
+        if old_start >= old_end {
+            continue;
+        }
+        let pos = |index| {
+            let (line, utf16_col) = content.line_utf16_col_for_content_index(index);
+            Position { line, utf16_col }
+        };
+        let loc = pos(old_start)..pos(old_end);
+
+        // replace the indentation of the output with the current one
+        let out_indent = out.line_indent(out_line);
+        let indent = content.line_indent(loc.start.line);
+        let mut new = String::new();
+        for (i, line) in formatted.split('\n').enumerate() {
+            if i > 0 {
+                new.push('\n');
+                if !line.is_empty() {
+                    new.push_str(indent);
+                }
+                new.push_str(line.strip_prefix(out_indent).unwrap_or(line));
+            } else {
+                new.push_str(line);
+            }
+        }
+        ret.push((loc, new));
+    }
+    Ok(ret)
+}
+
+fn tag_location_range(tag_location: &TagLocation) -> Range<Position> {
+    match tag_location.end.as_ref() {
+        None => tag_location.start.0.start..tag_location.start.1.end,
+        Some((_, x)) => tag_location.start.0.start..x.end,
+    }
+}
+
+fn node_item(node: &Node) -> (Range<Position>, Vec<&Node>) {
+    let children = match node {
+        Node::Element(elem) => elem.iter_children().collect(),
+        _ => vec![],
+    };
+    (node.location(), children)
+}
+
+fn offset_lsp_position(pos: lsp_types::Position, base: Position) -> lsp_types::Position {
+    if pos.line == 0 {
+        lsp_types::Position {
+            line: base.line,
+            character: base.utf16_col + pos.character,
+        }
+    } else {
+        lsp_types::Position {
+            line: base.line + pos.line,
+            character: pos.character,
+        }
+    }
+}
+
+/// Split `src` into lines with the line breaks included.
+///
+/// The lines are split in the same way as the positions are counted.
+fn split_lines(content: &FileContentMetadata) -> Vec<&str> {
+    let src = &content.content;
+    let line_starts = &content.line_starts;
+    let mut ret: Vec<&str> = line_starts
+        .iter()
+        .zip(line_starts[1..].iter().chain(Some(&src.len())))
+        .map(|(start, end)| &src[*start..*end])
+        .collect();
+    if ret.last() == Some(&"") {
+        ret.pop();
+    }
+    ret
+}
+
+/// Generate line-based text edits which turn `old` into `new` .
+fn diff_text_edits(old: &str, new: &str) -> Vec<TextEdit> {
+    let old = FileContentMetadata::new(old.to_string(), FileLang::Unknown);
+    let new = FileContentMetadata::new(new.to_string(), FileLang::Unknown);
+    let old_lines = split_lines(&old);
+    let new_lines = split_lines(&new);
+
+    // skip the common prefix and suffix
+    let prefix = old_lines
+        .iter()
+        .zip(new_lines.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    // find the matched lines with LCS
+    let mut matched: Vec<(usize, usize)> = vec![];
+    let width = new_mid.len() + 1;
+    if (old_mid.len() + 1) * width <= MAX_DIFF_TABLE_SIZE {
+        let mut table = vec![0u32; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                table[i * width + j] = if old_mid[i] == new_mid[j] {
+                    table[(i + 1) * width + j + 1] + 1
+                } else {
+                    table[(i + 1) * width + j].max(table[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                matched.push((i, j));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+    matched.push((old_mid.len(), new_mid.len()));
+
+    // convert unmatched segments to edits
+    let line_byte_index = |line: usize| {
+        if line < old_lines.len() {
+            old.line_starts[line]
+        } else {
+            old.content.len()
+        }
+    };
+    let mut ret = vec![];
+    let (mut i, mut j) = (0, 0);
+    for (mi, mj) in matched {
+        if mi > i || mj > j {
+            let start_line = prefix + i;
+            let end_line = prefix + mi;
+            let (start_line, start_col) =
+                old.line_utf16_col_for_content_index(line_byte_index(start_line));
+            let (end_line, end_col) =
+                old.line_utf16_col_for_content_index(line_byte_index(end_line));
+            let text_edit = TextEdit {
+                range: lsp_types::Range {
+                    start: lsp_types::Position {
+                        line: start_line,
+                        character: start_col,
+                    },
+                    end: lsp_types::Position {
+                        line: end_line,
+                        character: end_col,
+                    },
+                },
+                new_text: new_mid[j..mj].concat(),
+            };
+            ret.push(text_edit);
+        }
+        i = mi + 1;
+        j = mj + 1;
+    }
+    ret
+}
+
+/// Fix the indentation of the line according to the elements containing it.
+fn reindent_wxml_line(
+    template: &Template,
+    file_content: &FileContentMetadata,
+    line: u32,
+    ch: &str,
+    config: &FormatConfig,
+) -> Option<TextEdit> {
+    let src = &file_content.content;
+    let line_starts = &file_content.line_starts;
+    let line_start = *line_starts.get(line as usize)?;
+    let line_end = line_starts
+        .get(line as usize + 1)
+        .cloned()
+        .unwrap_or(src.len());
+    let line_content = src[line_start..line_end].trim_end_matches(['\r', '\n']);
+    let content = line_content.trim_start_matches([' ', '\t']);
+    let indent_len = line_content.len() - content.len();
+    match ch {
+        ">" if content.starts_with('<') => {}
+        "\n" if content.is_empty() || content.starts_with('<') => {}
+        _ => return None,
+    }
+
+    // count the tags containing the first non-whitespace character
+    let pos = Position {
+        line,
+        utf16_col: indent_len as u32,
+    };
+    let mut parent_starts: Vec<Position> = vec![];
+    let mut add_if_containing = |tag_location: &TagLocation| {
+        let (tag_start, tag_end) = &tag_location.start;
+        if pos < tag_end.end || parent_starts.contains(&tag_start.start) {
+            return;
+        }
+        let contains = match tag_location.end.as_ref() {
+            Some((end_tag_start, _)) => pos < end_tag_start.start,
+            None => {
+                // an unclosed tag (neither `/>` nor an end tag) contains all the following content
+                let self_closing = tag_location.close.start >= tag_start.start
+                    && tag_location.close.end <= tag_end.start;
+                !self_closing
+            }
+        };
+        if contains {
+            parent_starts.push(tag_start.start);
+        }
+    };
+    for sub in template.globals.sub_templates.iter() {
+        add_if_containing(&sub.tag_location);
+    }
+    for_each_template_element(template, |elem, _| {
+        add_if_containing(&elem.tag_location);
+    });
+
+    let options = config.stringify_options();
+    let unit = if options.use_tab_character {
+        "\t".to_string()
+    } else {
+        " ".repeat(options.tab_size as usize)
+    };
+    let expected = unit.repeat(parent_starts.len());
+    if line_content[..indent_len] == expected {
+        return None;
+    }
+    Some(TextEdit {
+        range: lsp_types::Range {
+            start: lsp_types::Position { line, character: 0 },
+            end: lsp_types::Position {
+                line,
+                character: indent_len as u32,
+            },
+        },
+        new_text: expected,
+    })
+}
@@ -4,6 +4,7 @@ use glass_easel_template_compiler::parse::{tag::ElementKind, Position};
 use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind, Url};
 
 use crate::{
+    component_script::PropertyDefinition,
     context::{backend_configuration::*, project::Project, FileLang},
     utils::location_to_lsp_range,
     wxml_utils::{ScopeKind, Token as WxmlToken, TokenStaticStyleValuePart},
@@ -58,7 +59,7 @@ fn md_str_hover_contents(s: impl Into<String>) -> HoverContents {
     })
 }
 
-fn reference_args_str(reference: &Option<Url>) -> String {
+pub(crate) fn reference_args_str(reference: &Option<Url>) -> String {
     if let Some(r) = reference {
         format!("\n\n[Reference]({})", r)
     } else {
@@ -66,6 +67,56 @@ fn reference_args_str(reference: &Option<Url>) -> String {
     }
 }
 
+fn attribute_hover_contents(attr: &AttributeConfig, kind: &str) -> HoverContents {
+    md_str_hover_contents(attribute_markdown(attr, kind))
+}
+
+/// The markdown description of an attribute or a property, with its type and metadata.
+pub(crate) fn attribute_markdown(attr: &AttributeConfig, kind: &str) -> String {
+    let AttributeConfig {
+        name,
+        ty,
+        default,
+        required,
+        since,
+        until,
+        description,
+        reference,
+        ..
+    } = attr;
+    let ty_args = if *ty != PropertyType::Any {
+        format!(": {}", ty)
+    } else {
+        format!("")
+    };
+    let required_args = if *required { " *(required)*" } else { "" };
+    let mut meta = vec![];
+    if let Some(x) = default {
+        meta.push(format!("Default: `{}`", x));
+    }
+    match (since, until) {
+        (Some(since), Some(until)) => meta.push(format!("Since {}, removed in {}", since, until)),
+        (Some(since), None) => meta.push(format!("Since {}", since)),
+        (None, Some(until)) => meta.push(format!("Removed in {}", until)),
+        (None, None) => {}
+    }
+    let meta_args = if meta.is_empty() {
+        format!("")
+    } else {
+        format!("\n\n{}", meta.join("  \n"))
+    };
+    format!(
+        "**{}**{} *{}*{}{}\n\n{}{}",
+        name,
+        ty_args,
+        kind,
+        required_args,
+        meta_args,
+        description,
+        reference_args_str(reference)
+    )
+}
+
 fn property_name_hint(
     backend_config: &BackendConfig,
     name: &str,
@@ -123,10 +174,21 @@ fn hover_wxml(
             })
         }
         WxmlToken::TagName(tag_name) => {
-            let contents = if let Some(_target_path) =
+            let contents = if project.is_component_generic(abs_path, &tag_name.name) {
+                plain_str_hover_contents("component generic")
+            } else if let Some(_target_path) =
                 project.get_target_component_path(abs_path, &tag_name.name)
             {
-                plain_str_hover_contents("custom component")
+                if let Some(placeholder) =
+                    project.get_component_placeholder(abs_path, &tag_name.name)
+                {
+                    plain_str_hover_contents(format!(
+                        "custom component (async, placeholder <{}>)",
+                        placeholder
+                    ))
+                } else {
+                    plain_str_hover_contents("custom component")
+                }
             } else if let Some(elem) = backend_config.search_component(&tag_name.name) {
                 let ComponentConfig {
                     tag_name,
@@ -179,51 +241,40 @@ fn hover_wxml(
                 if let Some(_target_path) =
                     project.get_target_component_path(abs_path, &tag_name.name)
                 {
-                    plain_str_hover_contents("custom component property")
+                    let prop = project
+                        .get_target_component_script(abs_path, &tag_name.name)
+                        .and_then(|x| x.find_property(&attr_name.name))
+                        .map(|x| x.1);
+                    if let Some(prop) = prop {
+                        let PropertyDefinition {
+                            name,
+                            ty,
+                            default_value,
+                            ..
+                        } = prop;
+                        let ty_args = match ty {
+                            Some(ty) => format!(": {}", ty),
+                            None => String::new(),
+                        };
+                        let default_args = match default_value {
+                            Some(x) => format!("\n\nDefault: `{}`", x),
+                            None => String::new(),
+                        };
+                        md_str_hover_contents(format!(
+                            "**{}**{} *custom component property*{}",
+                            name, ty_args, default_args
+                        ))
+                    } else {
+                        plain_str_hover_contents("custom component property")
+                    }
                 } else if let Some(prop) =
                     backend_config.search_property(&tag_name.name, &attr_name.name)
                 {
-                    let AttributeConfig {
-                        name,
-                        ty,
-                        description,
-                        reference,
-                        ..
-                    } = prop;
-                    let ty_args = if ty.len() > 0 {
-                        format!(": {}", ty)
-                    } else {
-                        format!("")
-                    };
-                    md_str_hover_contents(format!(
-                        "**{}**{} *property*\n\n{}{}",
-                        name,
-                        ty_args,
-                        description,
-                        reference_args_str(reference)
-                    ))
+                    attribute_hover_contents(prop, "property")
                 } else if let Some(attr) =
                     backend_config.search_attribute(&tag_name.name, &attr_name.name)
                 {
-                    let AttributeConfig {
-                        name,
-                        ty,
-                        description,
-                        reference,
-                        ..
-                    } = attr;
-                    let ty_args = if ty.len() > 0 {
-                        format!(": {}", ty)
-                    } else {
-                        format!("")
-                    };
-                    md_str_hover_contents(format!(
-                        "**{}**{} *attribute*\n\n{}{}",
-                        name,
-                        ty_args,
-                        description,
-                        reference_args_str(reference)
-                    ))
+                    attribute_hover_contents(attr, "attribute")
                 } else {
                     plain_str_hover_contents("unknown")
                 }
@@ -235,6 +286,31 @@ fn hover_wxml(
                 range: Some(location_to_lsp_range(&attr_name.location)),
             })
         }
+        WxmlToken::GenericName(generic_name, elem) => {
+            let generic = match &elem.kind {
+                ElementKind::Normal { tag_name, .. } => project
+                    .get_target_component_generics(abs_path, &tag_name.name)
+                    .and_then(|x| x.get(generic_name.name.as_str())),
+                _ => None,
+            };
+            let contents = if let Some(generic) = generic {
+                let default_args = if let Some(default) = generic.default_component() {
+                    format!("\n\nDefault component: `{}`", default)
+                } else {
+                    "\n\nRequired.".to_string()
+                };
+                md_str_hover_contents(format!(
+                    "**{}** *component generic*{}",
+                    generic_name.name, default_args
+                ))
+            } else {
+                plain_str_hover_contents("unknown")
+            };
+            Some(Hover {
+                contents,
+                range: Some(location_to_lsp_range(&generic_name.location)),
+            })
+        }
         WxmlToken::EventName(event_name, elem) => {
             let tag_name = match &elem.kind {
                 ElementKind::Normal { tag_name, .. } => Some(tag_name),
@@ -244,7 +320,15 @@ fn hover_wxml(
                 if let Some(_target_path) =
                     project.get_target_component_path(abs_path, &tag_name.name)
                 {
-                    plain_str_hover_contents("custom component event")
+                    let ev = project
+                        .get_target_component_script(abs_path, &tag_name.name)
+                        .and_then(|x| x.find_event(&event_name.name))
+                        .map(|x| x.1);
+                    if let Some(ev) = ev {
+                        md_str_hover_contents(format!("**{}** *custom component event*", ev.name))
+                    } else {
+                        plain_str_hover_contents("custom component event")
+                    }
                 } else if let Some(ev) =
                     backend_config.search_event(&tag_name.name, &event_name.name)
                 {
@@ -0,0 +1,172 @@
+use std::ops::Range;
+
+use compact_str::CompactString;
+
+use crate::{
+    context::project::FileContentMetadata,
+    wxss::{Location, Position},
+};
+
+/// The source locations of a key-value pair in a JSON file.
+#[derive(Debug, Clone)]
+pub(crate) struct JsonEntryLocation {
+    pub(crate) key: Location,
+    pub(crate) value: Location,
+}
+
+struct JsonScanner<'a> {
+    src: &'a str,
+    idx: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.idx).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.peek() {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            self.idx += 1;
+        }
+    }
+
+    fn scan_string(&mut self) -> Option<Range<usize>> {
+        let start = self.idx;
+        self.idx += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.idx += 2,
+                b'"' => {
+                    self.idx += 1;
+                    break;
+                }
+                _ => self.idx += 1,
+            }
+        }
+        Some(start..self.idx)
+    }
+
+    fn scan_value(
+        &mut self,
+        path: &mut Vec<CompactString>,
+        f: &mut impl FnMut(&[CompactString], Range<usize>, Range<usize>),
+    ) -> Option<Range<usize>> {
+        self.skip_whitespace();
+        let start = self.idx;
+        match self.peek()? {
+            b'{' => {
+                self.idx += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        b'}' => {
+                            self.idx += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.idx += 1;
+                        }
+                        b'"' => {
+                            let key_range = self.scan_string()?;
+                            let key: String =
+                                serde_json::from_str(&self.src[key_range.clone()]).ok()?;
+                            self.skip_whitespace();
+                            if self.peek()? != b':' {
+                                return None;
+                            }
+                            self.idx += 1;
+                            path.push(key.into());
+                            let value_range = self.scan_value(path, f);
+                            if let Some(value_range) = value_range.as_ref() {
+                                f(path, key_range, value_range.clone());
+                            }
+                            path.pop();
+                            value_range?;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            b'[' => {
+                self.idx += 1;
+                let mut index = 0usize;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        b']' => {
+                            self.idx += 1;
+                            break;
+                        }
+                        b',' => {
+                            self.idx += 1;
+                        }
+                        _ => {
+                            path.push(index.to_string().into());
+                            let value_range = self.scan_value(path, f);
+                            path.pop();
+                            value_range?;
+                            index += 1;
+                        }
+                    }
+                }
+            }
+            b'"' => {
+                self.scan_string()?;
+            }
+            _ => {
+                while let Some(b) = self.peek() {
+                    if b.is_ascii_whitespace() || b == b',' || b == b']' || b == b'}' {
+                        break;
+                    }
+                    self.idx += 1;
+                }
+                if self.idx == start {
+                    return None;
+                }
+            }
+        }
+        Some(start..self.idx)
+    }
+}
+
+/// Iterate over all key-value pairs in a JSON source.
+///
+/// The key path of each entry is provided, with array indexes written as decimal strings.
+/// Scanning stops at the first syntax error.
+pub(crate) fn for_each_json_entry(
+    content: &FileContentMetadata,
+    mut f: impl FnMut(&[CompactString], JsonEntryLocation),
+) {
+    let pos = |index| {
+        let (line, utf16_col) = content.line_utf16_col_for_content_index(index);
+        Position { line, utf16_col }
+    };
+    let mut scanner = JsonScanner {
+        src: &content.content,
+        idx: 0,
+    };
+    scanner.scan_value(&mut vec![], &mut |path, key, value| {
+        let loc = JsonEntryLocation {
+            key: pos(key.start)..pos(key.end),
+            value: pos(value.start)..pos(value.end),
+        };
+        f(path, loc);
+    });
+}
+
+/// Find the location of the entry with the specified key path in a JSON source.
+pub(crate) fn find_json_entry(
+    content: &FileContentMetadata,
+    key_path: &[&str],
+) -> Option<JsonEntryLocation> {
+    let mut ret = None;
+    for_each_json_entry(content, |path, loc| {
+        if ret.is_none() && path.iter().map(|x| x.as_str()).eq(key_path.iter().cloned()) {
+            ret = Some(loc);
+        }
+    });
+    ret
+}
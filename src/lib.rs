@@ -1,19 +1,34 @@
 use context::{
-    backend_configuration::BackendConfig, project::Project, ServerContext, ServerContextOptions,
+    backend_configuration::BackendConfig, project::Project, ClientSupport, ServerContext,
 };
-use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response, ResponseError};
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification, Request, RequestId, Response, ResponseError,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use tokio::task::AbortHandle;
 
+mod checker;
+mod cli;
+mod code_lens;
 mod color;
 mod completion;
+mod component_script;
+mod config;
 mod context;
+mod diagnostic;
 mod file;
 mod folding;
 mod formatting;
 mod hover;
+mod json_utils;
+mod link;
 mod logger;
 mod reference;
+mod selection;
 mod semantic;
+mod signature_help;
 mod symbol;
+mod transport;
 mod utils;
 mod wxml_ts;
 mod wxml_utils;
@@ -34,7 +49,7 @@ fn server_capabilities() -> lsp_types::ServerCapabilities {
         )),
         hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
         completion_provider: Some(lsp_types::CompletionOptions {
-            resolve_provider: None,
+            resolve_provider: Some(true),
             trigger_characters: Some(vec![
                 String::from("<"),
                 String::from("/"),
@@ -51,11 +66,13 @@ fn server_capabilities() -> lsp_types::ServerCapabilities {
             },
             completion_item: None,
         }),
-        // signature_help_provider: Some(lsp_types::SignatureHelpOptions {
-        //     trigger_characters: None,
-        //     retrigger_characters: None,
-        //     work_done_progress_options: lsp_types::WorkDoneProgressOptions { work_done_progress: None },
-        // }),
+        signature_help_provider: Some(lsp_types::SignatureHelpOptions {
+            trigger_characters: Some(vec![String::from("("), String::from(",")]),
+            retrigger_characters: None,
+            work_done_progress_options: lsp_types::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
         definition_provider: Some(lsp_types::OneOf::Left(true)),
         declaration_provider: Some(lsp_types::DeclarationCapability::Simple(true)),
         // type_definition_provider: Some(lsp_types::TypeDefinitionProviderCapability::Simple(true)),
@@ -63,7 +80,28 @@ fn server_capabilities() -> lsp_types::ServerCapabilities {
         references_provider: Some(lsp_types::OneOf::Left(true)),
         // document_highlight_provider: Some(lsp_types::OneOf::Left(true)),
         document_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        code_lens_provider: Some(lsp_types::CodeLensOptions {
+            resolve_provider: Some(false),
+        }),
+        selection_range_provider: Some(lsp_types::SelectionRangeProviderCapability::Simple(true)),
+        document_link_provider: Some(lsp_types::DocumentLinkOptions {
+            resolve_provider: Some(false),
+            work_done_progress_options: lsp_types::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
         color_provider: Some(lsp_types::ColorProviderCapability::Simple(true)),
+        diagnostic_provider: Some(lsp_types::DiagnosticServerCapabilities::Options(
+            lsp_types::DiagnosticOptions {
+                identifier: Some(String::from("glass-easel-analyzer")),
+                inter_file_dependencies: true,
+                workspace_diagnostics: true,
+                work_done_progress_options: lsp_types::WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+            },
+        )),
         folding_range_provider: Some(lsp_types::FoldingRangeProviderCapability::Simple(true)),
         semantic_tokens_provider: Some(
             lsp_types::SemanticTokensServerCapabilities::SemanticTokensOptions(
@@ -76,12 +114,16 @@ fn server_capabilities() -> lsp_types::ServerCapabilities {
                         token_modifiers: semantic::TOKEN_MODIFIERS.into(),
                     },
                     range: Some(true),
-                    full: Some(lsp_types::SemanticTokensFullOptions::Delta { delta: Some(false) }),
+                    full: Some(lsp_types::SemanticTokensFullOptions::Delta { delta: Some(true) }),
                 },
             ),
         ),
         document_formatting_provider: Some(lsp_types::OneOf::Left(true)),
-        // document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+        document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(lsp_types::DocumentOnTypeFormattingOptions {
+            first_trigger_character: String::from(">"),
+            more_trigger_character: Some(vec![String::from("\n")]),
+        }),
         workspace: Some(lsp_types::WorkspaceServerCapabilities {
             workspace_folders: Some(lsp_types::WorkspaceFoldersServerCapabilities {
                 supported: Some(true),
@@ -139,16 +181,33 @@ async fn handle_request(
     );
     handler!("textDocument/foldingRange", folding::folding_range);
     handler!("textDocument/semanticTokens/full", semantic::tokens_full);
+    handler!(
+        "textDocument/semanticTokens/full/delta",
+        semantic::tokens_full_delta
+    );
     handler!("textDocument/semanticTokens/range", semantic::tokens_range);
     handler!("textDocument/definition", reference::find_definition);
     handler!("textDocument/declaration", reference::find_declaration);
     handler!("textDocument/references", reference::find_references);
     handler!("textDocument/documentSymbol", symbol::document_symbol);
+    handler!("workspace/symbol", symbol::workspace_symbol);
+    handler!("textDocument/codeLens", code_lens::code_lens);
+    handler!("textDocument/documentLink", link::document_link);
+    handler!("textDocument/selectionRange", selection::selection_range);
+    handler!("textDocument/signatureHelp", signature_help::signature_help);
+    handler!("textDocument/diagnostic", diagnostic::document_diagnostic);
+    handler!("workspace/diagnostic", diagnostic::workspace_diagnostic);
     handler!("textDocument/hover", hover::hover);
     handler!("textDocument/completion", completion::completion);
+    handler!("completionItem/resolve", completion::completion_resolve);
     handler!("textDocument/documentColor", color::color);
     handler!("textDocument/colorPresentation", color::color_presentation);
     handler!("textDocument/formatting", formatting::formatting);
+    handler!("textDocument/rangeFormatting", formatting::range_formatting);
+    handler!(
+        "textDocument/onTypeFormatting",
+        formatting::on_type_formatting
+    );
 
     // method not found
     log::warn!("Missing LSP request handler for {:?}", method);
@@ -185,7 +244,6 @@ async fn handle_notification(
 
     // handlers for each method
     handler!("exit", noop);
-    handler!("$/cancelRequest", noop);
     handler!("$/setTrace", logger::set_trace);
     handler!("textDocument/didOpen", file::did_open);
     handler!("textDocument/didChange", file::did_change);
@@ -199,6 +257,10 @@ async fn handle_notification(
         "workspace/didChangeWorkspaceFolders",
         file::did_change_workspace_folders
     );
+    handler!(
+        "workspace/didChangeConfiguration",
+        config::did_change_configuration
+    );
 
     // method not found
     log::warn!("Missing LSP notification handler for {:?}", method);
@@ -212,6 +274,36 @@ fn generate_notification(method: impl Into<String>, params: impl serde::Serializ
     })
 }
 
+/// Abort a held request and generate the `RequestCanceled` response.
+///
+/// Other requests are handled in order, so they are already responded when the cancellation arrives.
+fn cancel_held_request(
+    held_requests: &RefCell<HashMap<RequestId, AbortHandle>>,
+    params: serde_json::Value,
+) -> Option<Response> {
+    let id: RequestId = match serde_json::from_value::<lsp_types::CancelParams>(params) {
+        Ok(x) => match x.id {
+            lsp_types::NumberOrString::Number(x) => x.into(),
+            lsp_types::NumberOrString::String(x) => x.into(),
+        },
+        Err(err) => {
+            log::error!("Invalid params on \"$/cancelRequest\": {}", err);
+            return None;
+        }
+    };
+    let handle = held_requests.borrow_mut().remove(&id)?;
+    handle.abort();
+    Some(Response {
+        id,
+        result: None,
+        error: Some(ResponseError {
+            code: ErrorCode::RequestCanceled as i32,
+            message: "the request is canceled".to_string(),
+            data: None,
+        }),
+    })
+}
+
 async fn cleanup(ctx: ServerContext, _params: serde_json::Value) -> anyhow::Result<()> {
     ctx.clear_all_projects().await;
     Ok(())
@@ -224,7 +316,14 @@ struct InitializeParams {
     #[serde(default)]
     initialization_options: InitializationOptions,
     capabilities: lsp_types::ClientCapabilities,
-    work_done_token: lsp_types::ProgressToken,
+    #[serde(default)]
+    work_done_token: Option<lsp_types::ProgressToken>,
+    #[serde(default)]
+    workspace_folders: Option<Vec<lsp_types::WorkspaceFolder>>,
+    #[serde(default)]
+    root_uri: Option<lsp_types::Url>,
+    #[serde(default)]
+    root_path: Option<String>,
 }
 
 #[derive(Default, serde::Deserialize)]
@@ -233,11 +332,49 @@ struct InitializeParams {
 struct InitializationOptions {
     #[serde(default)]
     backend_config: String,
+    #[serde(default)]
     workspace_folders: Vec<String>,
     #[serde(default)]
+    backend_config_path: Option<String>,
+    #[serde(default)]
+    backend_config_overlays: Vec<String>,
+    #[serde(default)]
     ignore_paths: Vec<String>,
     #[serde(default)]
-    enable_other_ss: bool,
+    enable_other_ss: Option<bool>,
+}
+
+impl InitializationOptions {
+    fn settings_layer(&self, base: Option<&std::path::Path>) -> config::SettingsLayer {
+        // prefer the path so that the file can be watched,
+        // while the content is used when the path is not given by any source
+        let backend_config = self
+            .backend_config_path
+            .as_ref()
+            .filter(|x| !x.is_empty())
+            .map(|x| {
+                Some(config::BackendConfigSource::Path(
+                    config::resolve_setting_path(x, base),
+                ))
+            });
+        config::SettingsLayer {
+            backend_config,
+            backend_config_overlays: Some(
+                self.backend_config_overlays
+                    .iter()
+                    .map(|x| config::resolve_setting_path(x, base))
+                    .collect(),
+            ),
+            ignore_paths: Some(
+                self.ignore_paths
+                    .iter()
+                    .map(|x| config::resolve_setting_path(x, base))
+                    .collect(),
+            ),
+            enable_other_ss: self.enable_other_ss,
+            default_backend_config: Some(self.backend_config.clone()).filter(|x| !x.is_empty()),
+        }
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -252,129 +389,224 @@ struct ProjectInfo<'a> {
     path: &'a str,
 }
 
-async fn serve() -> anyhow::Result<()> {
-    let (connection, _io_threads) = Connection::stdio();
-
-    // handshake
-    let (initialize_id, initialize_params) = connection.initialize_start()?;
-    let initialize_params: InitializeParams = serde_json::from_value(initialize_params)?;
-    let mut client_supported = true;
-    if initialize_params
-        .capabilities
-        .workspace
-        .as_ref()
-        .and_then(|x| x.workspace_folders)
-        != Some(true)
-    {
-        client_supported = false;
-    };
-    if initialize_params
-        .capabilities
-        .workspace
-        .as_ref()
-        .and_then(|x| {
-            x.did_change_watched_files
-                .and_then(|x| x.dynamic_registration)
-        })
-        != Some(true)
-    {
-        client_supported = false;
-    };
-    if initialize_params
-        .capabilities
-        .text_document
-        .as_ref()
-        .and_then(|x| x.definition.and_then(|x| x.link_support))
-        != Some(true)
-    {
-        client_supported = false;
-    };
-    if initialize_params
-        .capabilities
-        .text_document
-        .as_ref()
-        .and_then(|x| x.declaration.and_then(|x| x.link_support))
-        != Some(true)
-    {
-        client_supported = false;
+fn send_progress(
+    connection: &Connection,
+    token: Option<&lsp_types::ProgressToken>,
+    value: lsp_types::WorkDoneProgress,
+) {
+    let Some(token) = token else {
+        return;
     };
-    if !client_supported {
-        log::error!("The client does not have enough LSP capabilities");
-        return Err(anyhow::Error::msg("unsupported client"));
-    }
     connection
         .sender
         .send(generate_notification(
             "$/progress",
             lsp_types::ProgressParams {
-                token: initialize_params.work_done_token.clone(),
-                value: lsp_types::ProgressParamsValue::WorkDone(
-                    lsp_types::WorkDoneProgress::Begin(lsp_types::WorkDoneProgressBegin {
-                        title: "Initializing glass-easel-analyzer".to_string(),
-                        message: Some("initializing".to_string()),
-                        ..Default::default()
-                    }),
-                ),
+                token: token.clone(),
+                value: lsp_types::ProgressParamsValue::WorkDone(value),
             },
         ))
         .unwrap();
+}
 
-    // request workspace folders
-    let mut projects = vec![];
-    let ignore_paths: Vec<_> = initialize_params
-        .initialization_options
-        .ignore_paths
+/// The workspace folders, fallback to `rootUri` and `rootPath` if not provided.
+fn workspace_folder_uris(params: &InitializeParams) -> Vec<lsp_types::Url> {
+    let from_options = &params.initialization_options.workspace_folders;
+    if !from_options.is_empty() {
+        return from_options
+            .iter()
+            .filter_map(|uri| match lsp_types::Url::parse(uri) {
+                Ok(x) => Some(x),
+                Err(err) => {
+                    log::error!("Invalid workspace folder {:?}: {}", uri, err);
+                    None
+                }
+            })
+            .collect();
+    }
+    if let Some(folders) = params.workspace_folders.as_ref() {
+        if !folders.is_empty() {
+            return folders.iter().map(|x| x.uri.clone()).collect();
+        }
+    }
+    if let Some(uri) = params.root_uri.as_ref() {
+        return vec![uri.clone()];
+    }
+    params
+        .root_path
+        .as_ref()
+        .and_then(|x| lsp_types::Url::from_directory_path(x).ok())
+        .into_iter()
+        .collect()
+}
+
+fn register_capabilities(
+    connection: &Connection,
+    client_support: &ClientSupport,
+    backend_config_paths: &[&std::path::Path],
+) -> anyhow::Result<()> {
+    let mut registrations = vec![];
+    if client_support.watched_files_registration {
+        registrations.push(lsp_types::Registration {
+            id: "workspace/didChangeWatchedFiles".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: Some(serde_json::to_value(
+                lsp_types::DidChangeWatchedFilesRegistrationOptions {
+                    watchers: vec![
+                        lsp_types::FileSystemWatcher {
+                            glob_pattern: lsp_types::GlobPattern::String(
+                                "**/*.{json,wxml,wxss,js,ts}".to_string(),
+                            ),
+                            kind: Some(lsp_types::WatchKind::all()),
+                        },
+                        lsp_types::FileSystemWatcher {
+                            glob_pattern: lsp_types::GlobPattern::String(format!(
+                                "**/{}",
+                                config::CONFIG_FILE_NAME
+                            )),
+                            kind: Some(lsp_types::WatchKind::all()),
+                        },
+                    ],
+                },
+            )?),
+        });
+    }
+    registrations.extend(config::backend_config_watcher_registration(
+        client_support,
+        backend_config_paths,
+    ));
+    if client_support.did_change_configuration_registration {
+        registrations.push(lsp_types::Registration {
+            id: "workspace/didChangeConfiguration".to_string(),
+            method: "workspace/didChangeConfiguration".to_string(),
+            register_options: None,
+        });
+    }
+    if registrations.is_empty() {
+        return Ok(());
+    }
+    connection.sender.send(Message::Request(Request {
+        id: "client/registerCapability".to_string().into(),
+        method: "client/registerCapability".to_string(),
+        params: serde_json::to_value(lsp_types::RegistrationParams { registrations })?,
+    }))?;
+    Ok(())
+}
+
+async fn serve(connection: Connection) -> anyhow::Result<()> {
+    // handshake
+    let (initialize_id, initialize_params) = connection.initialize_start()?;
+    let initialize_params: InitializeParams = serde_json::from_value(initialize_params)?;
+    let capabilities = &initialize_params.capabilities;
+    let client_support = ClientSupport {
+        watched_files_registration: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| {
+                x.did_change_watched_files
+                    .and_then(|x| x.dynamic_registration)
+            })
+            .unwrap_or(false),
+        definition_link: capabilities
+            .text_document
+            .as_ref()
+            .and_then(|x| x.definition.and_then(|x| x.link_support))
+            .unwrap_or(false),
+        declaration_link: capabilities
+            .text_document
+            .as_ref()
+            .and_then(|x| x.declaration.and_then(|x| x.link_support))
+            .unwrap_or(false),
+        configuration: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| x.configuration)
+            .unwrap_or(false),
+        did_change_configuration_registration: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| {
+                x.did_change_configuration
+                    .and_then(|x| x.dynamic_registration)
+            })
+            .unwrap_or(false),
+        relative_pattern: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| {
+                x.did_change_watched_files
+                    .and_then(|x| x.relative_pattern_support)
+            })
+            .unwrap_or(false),
+        semantic_tokens_refresh: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| x.semantic_tokens.as_ref())
+            .and_then(|x| x.refresh_support)
+            .unwrap_or(false),
+        pull_diagnostics: capabilities
+            .text_document
+            .as_ref()
+            .is_some_and(|x| x.diagnostic.is_some()),
+        diagnostic_refresh: capabilities
+            .workspace
+            .as_ref()
+            .and_then(|x| x.diagnostic.as_ref())
+            .and_then(|x| x.refresh_support)
+            .unwrap_or(false),
+    };
+    if !client_support.watched_files_registration {
+        log::warn!("The client does not support watching files, so changes outside the editor may not be noticed");
+    }
+    let work_done_token = initialize_params.work_done_token.as_ref();
+    send_progress(
+        &connection,
+        work_done_token,
+        lsp_types::WorkDoneProgress::Begin(lsp_types::WorkDoneProgressBegin {
+            title: "Initializing glass-easel-analyzer".to_string(),
+            message: Some("initializing".to_string()),
+            ..Default::default()
+        }),
+    );
+
+    // resolve settings
+    let workspace_folders: Vec<_> = workspace_folder_uris(&initialize_params)
         .iter()
-        .map(|x| std::path::PathBuf::from(x))
+        .map(|p_uri| {
+            lsp_types::Url::to_file_path(p_uri)
+                .unwrap_or_else(|_| crate::utils::generate_non_fs_fake_path(p_uri))
+        })
         .collect();
-    let enable_other_ss = initialize_params.initialization_options.enable_other_ss;
-    let server_context_options = ServerContextOptions {
-        ignore_paths,
-        enable_other_ss,
-    };
-    for uri in initialize_params
+    let editor_settings = initialize_params
         .initialization_options
-        .workspace_folders
-        .iter()
-    {
-        let p_uri = lsp_types::Url::parse(uri).unwrap();
-        let p = lsp_types::Url::to_file_path(&p_uri)
-            .unwrap_or_else(|_| crate::utils::generate_non_fs_fake_path(&p_uri));
+        .settings_layer(workspace_folders.first().map(|x| x.as_path()));
+    let settings = config::ServerSettings::resolve(&workspace_folders, &editor_settings);
+    let server_context_options = settings.options.clone();
+
+    // request workspace folders
+    let mut projects = vec![];
+    for p in workspace_folders.iter() {
         let name = p.file_name().and_then(|x| x.to_str()).unwrap_or_default();
-        connection
-            .sender
-            .send(generate_notification(
-                "$/progress",
-                lsp_types::ProgressParams {
-                    token: initialize_params.work_done_token.clone(),
-                    value: lsp_types::ProgressParamsValue::WorkDone(
-                        lsp_types::WorkDoneProgress::Report(lsp_types::WorkDoneProgressReport {
-                            message: Some(format!("scanning components in {:?}", name)),
-                            ..Default::default()
-                        }),
-                    ),
-                },
-            ))
-            .unwrap();
-        let found_projects = Project::search_projects(&p, &server_context_options).await;
+        send_progress(
+            &connection,
+            work_done_token,
+            lsp_types::WorkDoneProgress::Report(lsp_types::WorkDoneProgressReport {
+                message: Some(format!("scanning components in {:?}", name)),
+                ..Default::default()
+            }),
+        );
+        let found_projects = Project::search_projects(p, &server_context_options).await;
         if found_projects.len() == 0 {
             continue;
         }
-        connection
-            .sender
-            .send(generate_notification(
-                "$/progress",
-                lsp_types::ProgressParams {
-                    token: initialize_params.work_done_token.clone(),
-                    value: lsp_types::ProgressParamsValue::WorkDone(
-                        lsp_types::WorkDoneProgress::Report(lsp_types::WorkDoneProgressReport {
-                            message: Some(format!("loading components in {}", name)),
-                            ..Default::default()
-                        }),
-                    ),
-                },
-            ))
-            .unwrap();
+        send_progress(
+            &connection,
+            work_done_token,
+            lsp_types::WorkDoneProgress::Report(lsp_types::WorkDoneProgressReport {
+                message: Some(format!("loading components in {}", name)),
+                ..Default::default()
+            }),
+        );
         for mut project in found_projects {
             project.init().await;
             projects.push(project);
@@ -382,21 +614,14 @@ async fn serve() -> anyhow::Result<()> {
     }
 
     // send initialize done
-    connection
-        .sender
-        .send(generate_notification(
-            "$/progress",
-            lsp_types::ProgressParams {
-                token: initialize_params.work_done_token.clone(),
-                value: lsp_types::ProgressParamsValue::WorkDone(lsp_types::WorkDoneProgress::End(
-                    lsp_types::WorkDoneProgressEnd {
-                        message: Some("finished".to_string()),
-                        ..Default::default()
-                    },
-                )),
-            },
-        ))
-        .unwrap();
+    send_progress(
+        &connection,
+        work_done_token,
+        lsp_types::WorkDoneProgress::End(lsp_types::WorkDoneProgressEnd {
+            message: Some("finished".to_string()),
+            ..Default::default()
+        }),
+    );
     let initialize_result = lsp_types::InitializeResult {
         capabilities: server_capabilities(),
         server_info: Some(lsp_types::ServerInfo {
@@ -407,45 +632,14 @@ async fn serve() -> anyhow::Result<()> {
     connection.initialize_finish(initialize_id, serde_json::to_value(initialize_result)?)?;
 
     // register capabilities
-    let registrations = lsp_types::RegistrationParams {
-        registrations: vec![lsp_types::Registration {
-            id: "workspace/didChangeWatchedFiles".to_string(),
-            method: "workspace/didChangeWatchedFiles".to_string(),
-            register_options: Some(serde_json::to_value(
-                lsp_types::DidChangeWatchedFilesRegistrationOptions {
-                    watchers: vec![lsp_types::FileSystemWatcher {
-                        glob_pattern: lsp_types::GlobPattern::String(
-                            "**/*.{json,wxml,wxss}".to_string(),
-                        ),
-                        kind: Some(lsp_types::WatchKind::all()),
-                    }],
-                },
-            )?),
-        }],
-    };
-    connection.sender.send(Message::Request(Request {
-        id: "client/registerCapability".to_string().into(),
-        method: "client/registerCapability".to_string(),
-        params: serde_json::to_value(registrations)?,
-    }))?;
+    register_capabilities(
+        &connection,
+        &client_support,
+        &settings.backend_config.paths(),
+    )?;
 
     // parse backend configuration
-    let has_backend_config = !initialize_params
-        .initialization_options
-        .backend_config
-        .is_empty();
-    let backend_config = if !has_backend_config {
-        log::warn!("Missing glass-easel backend configuration");
-        Default::default()
-    } else {
-        match BackendConfig::parse_str(&initialize_params.initialization_options.backend_config) {
-            Ok(x) => x,
-            Err(err) => {
-                log::error!("Failed to parse glass-easel backend configuration: {}", err);
-                Default::default()
-            }
-        }
-    };
+    let (backend_config, backend_config_diagnostics) = settings.load_backend_config();
 
     // send initialized projects
     let template_backend_config = backend_config.generate_template_backend_config();
@@ -488,9 +682,20 @@ async fn serve() -> anyhow::Result<()> {
             backend_config,
             projects,
             server_context_options.clone(),
+            client_support,
+            workspace_folders,
+            editor_settings,
         );
+        server_context.set_backend_config_layers(settings.backend_config.clone());
         (server_context, sender)
     };
+    let backend_config_diagnostics: Vec<_> = backend_config_diagnostics
+        .into_iter()
+        .filter(|(_, diagnostics)| !diagnostics.is_empty())
+        .collect();
+    if !backend_config_diagnostics.is_empty() {
+        config::publish_backend_config_diagnostics(&server_context, backend_config_diagnostics);
+    }
 
     // from this on the log can be shown in the lsp client
     logger::set_trace(
@@ -501,28 +706,58 @@ async fn serve() -> anyhow::Result<()> {
     )
     .await?;
 
+    // the editor settings may be different from the initialization options
+    if server_context.client_support().configuration {
+        tokio::task::spawn_local(config::pull_client_settings(server_context.clone()));
+    }
+
     // waiting requests on a separate thread
     let connection_thread = {
         let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
         tokio::task::spawn_local(async move {
+            // the requests running in separate tasks, which can be canceled
+            let held_requests: Rc<RefCell<HashMap<RequestId, AbortHandle>>> = Default::default();
             while let Some(msg) = receiver.recv().await {
                 let ctx = server_context.clone();
                 match msg {
-                    Message::Request(req) => match handle_request(ctx, req).await {
-                        Ok(res) => {
-                            if let Err(err) = lsp_sender.send(Message::Response(res)) {
-                                log::error!("{}", err);
+                    Message::Request(req) => {
+                        // the workspace diagnostic request may be held until anything changes,
+                        // so it should not block the following messages
+                        let held = req.method == "workspace/diagnostic";
+                        let id = req.id.clone();
+                        let lsp_sender = lsp_sender.clone();
+                        let held_requests2 = held_requests.clone();
+                        let f = async move {
+                            let id = req.id.clone();
+                            let ret = handle_request(ctx, req).await;
+                            held_requests2.borrow_mut().remove(&id);
+                            match ret {
+                                Ok(res) => {
+                                    if let Err(err) = lsp_sender.send(Message::Response(res)) {
+                                        log::error!("{}", err);
+                                    }
+                                }
+                                Err(err) => {
+                                    log::error!("{}", err);
+                                }
                             }
+                        };
+                        if held {
+                            let handle = tokio::task::spawn_local(f).abort_handle();
+                            held_requests.borrow_mut().insert(id, handle);
+                        } else {
+                            f.await;
                         }
-                        Err(err) => {
-                            log::error!("{}", err);
-                        }
-                    },
-                    Message::Response(Response {
-                        id: _,
-                        result: _,
-                        error,
-                    }) => {
+                    }
+                    Message::Response(res) => {
+                        let Some(Response {
+                            id: _,
+                            result: _,
+                            error,
+                        }) = ctx.handle_response(res)
+                        else {
+                            continue;
+                        };
                         if let Some(err) = error {
                             log::error!("LSP response error: {:?}", err.message);
                         }
@@ -532,6 +767,14 @@ async fn serve() -> anyhow::Result<()> {
                         if note.method == "exit" {
                             break;
                         }
+                        if note.method == "$/cancelRequest" {
+                            if let Some(res) = cancel_held_request(&held_requests, note.params) {
+                                if let Err(err) = lsp_sender.send(Message::Response(res)) {
+                                    log::error!("{}", err);
+                                }
+                            }
+                            continue;
+                        }
                         if let Err(err) = handle_notification(ctx, note).await {
                             log::error!("{}", err);
                         }
@@ -559,8 +802,17 @@ async fn serve() -> anyhow::Result<()> {
     Ok(())
 }
 
+pub use cli::{run_command, Command};
+pub use transport::TransportArgs;
+
 pub async fn run() -> anyhow::Result<()> {
+    run_with_transport(Default::default()).await
+}
+
+pub async fn run_with_transport(transport: TransportArgs) -> anyhow::Result<()> {
     logger::init_trace();
     let local = tokio::task::LocalSet::new();
-    local.run_until(serve()).await
+    local
+        .run_until(transport::serve_with(transport, serve))
+        .await
 }
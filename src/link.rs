@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use glass_easel_template_compiler::parse::tag::{ElementKind, Script, Value};
+use lsp_types::{DocumentLink, DocumentLinkParams};
+
+use crate::{
+    context::{project::Project, FileLang},
+    utils::{ensure_file_extension, location_to_lsp_range},
+    wxml_utils::for_each_template_element,
+    wxss::{token::TokenTree, Location, MaybeUnknown, Rule},
+    wxss_utils::{for_each_property_in_style_sheet, for_each_rule_in_style_sheet},
+    ServerContext,
+};
+
+/// The attributes which contain static asset paths in WXML.
+const ASSET_ATTRIBUTE_NAMES: [&str; 2] = ["src", "poster"];
+
+pub(crate) async fn document_link(
+    ctx: ServerContext,
+    params: DocumentLinkParams,
+) -> anyhow::Result<Vec<DocumentLink>> {
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Vec<DocumentLink>> {
+                let links = match file_lang {
+                    FileLang::Wxml => collect_wxml_links(project, &abs_path),
+                    FileLang::Wxss | FileLang::OtherSs => collect_wxss_links(project, &abs_path),
+                    _ => vec![],
+                };
+                Ok(links)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+/// Resolve a path in the source file against the file or the project root.
+///
+/// URLs with a scheme (like `https:` or `data:`) are not resolved.
+fn resolve_link_target(
+    project: &Project,
+    abs_path: &Path,
+    src: &str,
+    ext: Option<&str>,
+) -> Option<lsp_types::Url> {
+    let src = src.trim();
+    let src = src.split(['?', '#']).next().unwrap_or_default();
+    if src.is_empty() || src.starts_with("//") || src.contains("{{") {
+        return None;
+    }
+    if let Some((scheme, _)) = src.split_once(':') {
+        if !scheme.contains('/') {
+            return None;
+        }
+    }
+    let p = project.find_rel_path_for_file(abs_path, src)?;
+    let p = match ext {
+        Some(ext) => ensure_file_extension(&p, ext)?.to_path_buf(),
+        None => p,
+    };
+    lsp_types::Url::from_file_path(p).ok()
+}
+
+fn collect_wxml_links(project: &Project, abs_path: &Path) -> Vec<DocumentLink> {
+    let mut ret = vec![];
+    let Ok(template) = project.get_wxml_tree(abs_path) else {
+        return ret;
+    };
+    let mut add = |loc: &Location, src: &str, ext: Option<&str>| {
+        if let Some(target) = resolve_link_target(project, abs_path, src, ext) {
+            ret.push(DocumentLink {
+                range: location_to_lsp_range(loc),
+                target: Some(target),
+                tooltip: None,
+                data: None,
+            });
+        }
+    };
+    for i in template.globals.imports.iter() {
+        add(&i.src.location, &i.src.name, Some("wxml"));
+    }
+    for i in template.globals.includes.iter() {
+        add(&i.src.location, &i.src.name, Some("wxml"));
+    }
+    for i in template.globals.scripts.iter() {
+        if let Script::GlobalRef { src, .. } = i {
+            add(&src.location, &src.name, Some("wxs"));
+        }
+    }
+    for_each_template_element(template, |elem, _| {
+        let ElementKind::Normal { attributes, .. } = &elem.kind else {
+            return;
+        };
+        for attr in attributes.iter() {
+            if !ASSET_ATTRIBUTE_NAMES.contains(&attr.name.name.as_str()) {
+                continue;
+            }
+            if let Some(Value::Static {
+                value, location, ..
+            }) = attr.value.as_ref()
+            {
+                add(location, value, None);
+            }
+        }
+    });
+    ret
+}
+
+fn collect_wxss_links(project: &Project, abs_path: &Path) -> Vec<DocumentLink> {
+    let mut ret = vec![];
+    let Ok(sheet) = project.get_style_sheet(abs_path, false) else {
+        return ret;
+    };
+    let mut add = |loc: &Location, src: &str, ext: Option<&str>| {
+        if let Some(target) = resolve_link_target(project, abs_path, src, ext) {
+            ret.push(DocumentLink {
+                range: location_to_lsp_range(loc),
+                target: Some(target),
+                tooltip: None,
+                data: None,
+            });
+        }
+    };
+    for_each_rule_in_style_sheet(sheet, |rule| {
+        if let Rule::Import(x) = rule {
+            if let MaybeUnknown::Normal(url, _) = &x.url {
+                add(&url.location, &url.content, Some("wxss"));
+            }
+        }
+    });
+    fn rec_in_token_tree(tt: &TokenTree, f: &mut impl FnMut(&TokenTree)) {
+        f(tt);
+        if let Some(children) = tt.children() {
+            for child in children.iter() {
+                rec_in_token_tree(child, f);
+            }
+        }
+    }
+    for_each_property_in_style_sheet(sheet, |prop| {
+        for tt in prop.value.iter() {
+            rec_in_token_tree(tt, &mut |tt| match tt {
+                TokenTree::UnquotedUrl(x) => add(&x.location, &x.content, None),
+                TokenTree::Function(x) if x.name.eq_ignore_ascii_case("url") => {
+                    if let Some(TokenTree::QuotedString(s)) = x.children.first() {
+                        add(&s.location, &s.content, None);
+                    }
+                }
+                _ => {}
+            });
+        }
+    });
+    ret
+}
@@ -2,15 +2,31 @@ use clap::Parser;
 
 #[derive(clap::Parser)]
 #[clap(version, about)]
-struct Args {}
+struct Args {
+    #[command(subcommand)]
+    command: Option<glass_easel_analyzer::Command>,
+    #[command(flatten)]
+    transport: glass_easel_analyzer::TransportArgs,
+}
 
 fn main() {
-    let _args = Args::parse();
+    let args = Args::parse();
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap();
-    if let Err(err) = runtime.block_on(async { glass_easel_analyzer::run().await }) {
+    if let Some(command) = args.command {
+        match runtime.block_on(glass_easel_analyzer::run_command(command)) {
+            Ok(code) => std::process::exit(code),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(2);
+            }
+        }
+    }
+    if let Err(err) =
+        runtime.block_on(async { glass_easel_analyzer::run_with_transport(args.transport).await })
+    {
         eprintln!("{}", err);
         std::process::exit(1);
     } else {
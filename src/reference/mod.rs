@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use lsp_types::{GotoDefinitionParams, Location, LocationLink, ReferenceParams};
+use lsp_types::{
+    GotoDefinitionParams, GotoDefinitionResponse, Location, LocationLink, ReferenceParams,
+};
 
 use crate::{
     context::{project::Project, FileLang},
@@ -10,10 +12,25 @@ use crate::{
 mod wxml;
 mod wxss;
 
+/// Convert links to plain locations if the client does not support links.
+fn links_to_response(links: Vec<LocationLink>, link_support: bool) -> GotoDefinitionResponse {
+    if link_support {
+        return GotoDefinitionResponse::Link(links);
+    }
+    let locations = links
+        .into_iter()
+        .map(|x| Location {
+            uri: x.target_uri,
+            range: x.target_selection_range,
+        })
+        .collect();
+    GotoDefinitionResponse::Array(locations)
+}
+
 pub(crate) async fn find_definition(
     ctx: ServerContext,
     params: GotoDefinitionParams,
-) -> anyhow::Result<Vec<LocationLink>> {
+) -> anyhow::Result<GotoDefinitionResponse> {
     let uri = &params.text_document_position_params.text_document.uri;
     let position = params.text_document_position_params.position.clone();
     let ret = ctx
@@ -32,13 +49,13 @@ pub(crate) async fn find_definition(
             },
         )
         .await??;
-    Ok(ret)
+    Ok(links_to_response(ret, ctx.client_support().definition_link))
 }
 
 pub(crate) async fn find_declaration(
     ctx: ServerContext,
     params: GotoDefinitionParams,
-) -> anyhow::Result<Vec<LocationLink>> {
+) -> anyhow::Result<GotoDefinitionResponse> {
     let uri = &params.text_document_position_params.text_document.uri;
     let position = params.text_document_position_params.position.clone();
     let ret = ctx
@@ -57,7 +74,10 @@ pub(crate) async fn find_declaration(
             },
         )
         .await??;
-    Ok(ret)
+    Ok(links_to_response(
+        ret,
+        ctx.client_support().declaration_link,
+    ))
 }
 
 pub(crate) async fn find_references(
@@ -84,3 +104,22 @@ pub(crate) async fn find_references(
         .await??;
     Ok(ret)
 }
+
+/// Find all elements using the component defined by the WXML file.
+pub(crate) fn component_usages(project: &Project, wxml_path: &Path) -> Vec<Location> {
+    wxml::find_component_usages(project, &wxml_path.with_extension(""))
+}
+
+/// Find all `<template is>` usages of the template defined in the WXML file.
+pub(crate) fn template_usages(project: &Project, wxml_path: &Path, name: &str) -> Vec<Location> {
+    wxml::find_template_usages(project, wxml_path, name)
+}
+
+/// Find all elements in the sibling WXML file that use the class.
+pub(crate) fn class_usages(project: &Project, wxss_path: &Path, name: &str) -> Vec<Location> {
+    let wxml_path = wxss_path.with_extension("wxml");
+    match project.get_wxml_tree(&wxml_path) {
+        Ok(template) => wxml::find_elements_matching_class(project, &wxml_path, template, name),
+        Err(_) => vec![],
+    }
+}
@@ -114,6 +114,42 @@ fn find_slot_matching<'a>(
     Some((target_wxml_path, ret))
 }
 
+fn find_slot_definition(
+    project: &Project,
+    abs_path: &Path,
+    tag_name: &str,
+    slot_name: &str,
+) -> Option<(PathBuf, Vec<std::ops::Range<Position>>)> {
+    let mut ret = vec![];
+    let target_path = project.get_target_component_path(abs_path, tag_name)?;
+    let target_wxml_path = add_file_extension(&target_path, "wxml")?;
+    let template = project.get_wxml_tree(&target_wxml_path).ok()?;
+    for_each_slot(template, |slot_elem| {
+        let ElementKind::Slot { name, .. } = &slot_elem.kind else {
+            return;
+        };
+        let Value::Static {
+            value, location, ..
+        } = &name.1
+        else {
+            return;
+        };
+        if value.as_str() == slot_name {
+            ret.push(location.clone());
+        }
+    });
+    Some((target_wxml_path, ret))
+}
+
+fn find_component_generic_declaration(
+    project: &Project,
+    json_path: &Path,
+    generic_name: &str,
+) -> Option<lsp_types::Range> {
+    let entry = project.find_json_entry(json_path, &["componentGenerics", generic_name])?;
+    Some(location_to_lsp_range(&entry.key))
+}
+
 fn get_target_template_path<'a>(
     project: &'a Project,
     abs_path: &Path,
@@ -172,6 +208,35 @@ fn search_wxml_template_usages(
     }
 }
 
+pub(super) fn find_template_usages(project: &Project, abs_path: &Path, is: &str) -> Vec<Location> {
+    let mut ret = vec![];
+    search_wxml_template_usages(project, abs_path, is, |target_wxml, loc| {
+        ret.push(Location {
+            uri: lsp_types::Url::from_file_path(&target_wxml).unwrap(),
+            range: location_to_lsp_range(&loc),
+        });
+    });
+    ret
+}
+
+pub(super) fn find_component_usages(project: &Project, target_path: &Path) -> Vec<Location> {
+    let mut ret = vec![];
+    project.search_component_wxml_usages_of_target(
+        target_path,
+        |source_wxml, template, expected_tag_name| {
+            crate::wxml_utils::for_each_tag_name(template, |tag_name| {
+                if tag_name.name.as_str() == expected_tag_name {
+                    ret.push(Location {
+                        uri: lsp_types::Url::from_file_path(source_wxml).unwrap(),
+                        range: location_to_lsp_range(&tag_name.location),
+                    });
+                }
+            });
+        },
+    );
+    ret
+}
+
 pub(super) fn find_declaration(
     project: &mut Project,
     abs_path: &Path,
@@ -189,6 +254,19 @@ pub(super) fn find_declaration(
         );
         match token {
             Token::TagName(ident) => {
+                if project.is_component_generic(abs_path, &ident.name) {
+                    let json_path = abs_path.with_extension("json");
+                    if let Some(target_range) =
+                        find_component_generic_declaration(project, &json_path, &ident.name)
+                    {
+                        ret.push(LocationLink {
+                            origin_selection_range: Some(location_to_lsp_range(&ident.location)),
+                            target_uri: lsp_types::Url::from_file_path(&json_path).unwrap(),
+                            target_range,
+                            target_selection_range: target_range,
+                        });
+                    }
+                }
                 if let Some(target_path) = project.get_target_component_path(abs_path, &ident.name)
                 {
                     if let Some(target_wxml_path) = add_file_extension(&target_path, "wxml") {
@@ -211,6 +289,121 @@ pub(super) fn find_declaration(
                     }
                 }
             }
+            Token::GenericName(name, elem) => {
+                if let ElementKind::Normal { tag_name, .. } = &elem.kind {
+                    let target_json_path = project
+                        .get_target_component_path(abs_path, &tag_name.name)
+                        .and_then(|x| add_file_extension(&x, "json"));
+                    if let Some(target_json_path) = target_json_path {
+                        if let Some(target_range) = find_component_generic_declaration(
+                            project,
+                            &target_json_path,
+                            &name.name,
+                        ) {
+                            ret.push(LocationLink {
+                                origin_selection_range: Some(location_to_lsp_range(&name.location)),
+                                target_uri: lsp_types::Url::from_file_path(target_json_path)
+                                    .unwrap(),
+                                target_range,
+                                target_selection_range: target_range,
+                            });
+                        }
+                    }
+                }
+            }
+            Token::GenericRef(value, _) => {
+                if let Some(target_path) = project.get_target_component_path(abs_path, &value.name)
+                {
+                    if let Some(target_wxml_path) = add_file_extension(&target_path, "wxml") {
+                        let target_range = lsp_types::Range::new(
+                            lsp_types::Position {
+                                line: 0,
+                                character: 0,
+                            },
+                            lsp_types::Position {
+                                line: 0,
+                                character: 0,
+                            },
+                        );
+                        ret.push(LocationLink {
+                            origin_selection_range: Some(location_to_lsp_range(&value.location)),
+                            target_uri: lsp_types::Url::from_file_path(target_wxml_path).unwrap(),
+                            target_range,
+                            target_selection_range: target_range,
+                        });
+                    }
+                }
+            }
+            Token::AttributeName(name, elem) | Token::EventName(name, elem) => {
+                if let ElementKind::Normal { tag_name, .. } = &elem.kind {
+                    if let Some(script) =
+                        project.get_target_component_script(abs_path, &tag_name.name)
+                    {
+                        let target = if let Token::AttributeName(..) = token {
+                            script
+                                .find_property(&name.name)
+                                .map(|(p, x)| (p, &x.location))
+                        } else {
+                            script.find_event(&name.name).map(|(p, x)| (p, &x.location))
+                        };
+                        if let Some((target_path, target_loc)) = target {
+                            let target_range = location_to_lsp_range(target_loc);
+                            ret.push(LocationLink {
+                                origin_selection_range: Some(location_to_lsp_range(&name.location)),
+                                target_uri: lsp_types::Url::from_file_path(target_path).unwrap(),
+                                target_range,
+                                target_selection_range: target_range,
+                            });
+                        }
+                    }
+                }
+            }
+            Token::StaticEventHandler(loc, name, _) => {
+                if let Some((target_path, method)) = project
+                    .get_own_component_script(abs_path)
+                    .and_then(|x| x.find_method(name.trim()))
+                {
+                    let target_range = location_to_lsp_range(&method.location);
+                    ret.push(LocationLink {
+                        origin_selection_range: Some(location_to_lsp_range(&loc)),
+                        target_uri: lsp_types::Url::from_file_path(target_path).unwrap(),
+                        target_range,
+                        target_selection_range: target_range,
+                    });
+                }
+            }
+            Token::EventHandler(value, _) => {
+                if let Some((target_path, method)) = project
+                    .get_own_component_script(abs_path)
+                    .and_then(|x| x.find_method(&value.name))
+                {
+                    let target_range = location_to_lsp_range(&method.location);
+                    ret.push(LocationLink {
+                        origin_selection_range: Some(location_to_lsp_range(&value.location)),
+                        target_uri: lsp_types::Url::from_file_path(target_path).unwrap(),
+                        target_range,
+                        target_selection_range: target_range,
+                    });
+                }
+            }
+            Token::SlotName(loc, name, parent) => {
+                if let ElementKind::Normal { tag_name, .. } = &parent.kind {
+                    if let Some((target_wxml_path, ranges)) =
+                        find_slot_definition(project, abs_path, &tag_name.name, name)
+                    {
+                        for target_range in ranges {
+                            let target_range = location_to_lsp_range(&target_range);
+                            ret.push(LocationLink {
+                                origin_selection_range: Some(location_to_lsp_range(&loc)),
+                                target_uri: lsp_types::Url::from_file_path(&target_wxml_path)
+                                    .unwrap(),
+                                target_range,
+                                target_selection_range: target_range,
+                            });
+                        }
+                    }
+                }
+            }
             Token::StaticId(loc, _) | Token::StaticClassName(loc, _, _) => {
                 ret.push(LocationLink {
                     origin_selection_range: Some(location_to_lsp_range(&loc)),
@@ -611,12 +804,8 @@ pub(super) fn find_references(
                 }
             }
             Token::TemplateName(name) => {
-                search_wxml_template_usages(project, abs_path, &name.name, |target_wxml, loc| {
-                    ret.push(Location {
-                        uri: lsp_types::Url::from_file_path(&target_wxml).unwrap(),
-                        range: location_to_lsp_range(&loc),
-                    });
-                });
+                let mut x = find_template_usages(project, abs_path, &name.name);
+                ret.append(&mut x);
             }
             Token::TemplateRef(is, _) => {
                 if let Some((abs_path, _)) =
@@ -0,0 +1,322 @@
+use glass_easel_template_compiler::parse::{
+    tag::{Node, Script, TagLocation},
+    Template, TemplateStructure,
+};
+use lsp_types::{SelectionRange, SelectionRangeParams};
+
+use crate::{
+    context::{project::FileContentMetadata, FileLang},
+    utils::{inclusive_contains, location_to_lsp_range},
+    wxml_utils::{
+        for_each_attribute_in_element, for_each_template_element,
+        for_each_template_expression_in_subtree, for_each_template_root,
+        for_each_template_value_in_subtree,
+    },
+    wxss::{
+        keyframe::Keyframe,
+        token::{BraceOrSemicolon, TokenTree},
+        CSSParse, List, Location, Position, Rule, RuleOrProperty, StyleSheet,
+    },
+};
+
+pub(crate) async fn selection_range(
+    ctx: crate::ServerContext,
+    params: SelectionRangeParams,
+) -> anyhow::Result<Vec<SelectionRange>> {
+    let positions = params.positions;
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Vec<SelectionRange>> {
+                let ret = positions
+                    .into_iter()
+                    .map(|pos| {
+                        let pos = Position {
+                            line: pos.line,
+                            utf16_col: pos.character,
+                        };
+                        let locations = match file_lang {
+                            FileLang::Wxml => match (
+                                project.get_wxml_tree(&abs_path),
+                                project.cached_file_content(&abs_path),
+                            ) {
+                                (Ok(template), Some(content)) => {
+                                    collect_wxml_locations(template, content, pos)
+                                }
+                                _ => vec![],
+                            },
+                            FileLang::Wxss | FileLang::OtherSs => {
+                                match project.get_style_sheet(&abs_path, false) {
+                                    Ok(sheet) => collect_wxss_locations(sheet, pos),
+                                    Err(_) => vec![],
+                                }
+                            }
+                            _ => vec![],
+                        };
+                        build_selection_range(locations, pos)
+                    })
+                    .collect();
+                Ok(ret)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+/// Chain the locations which contain the position from the outermost to the innermost one.
+fn build_selection_range(mut locations: Vec<Location>, pos: Position) -> SelectionRange {
+    locations.retain(|x| x.start < x.end && inclusive_contains(x, pos));
+    locations.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    locations.dedup();
+    let mut ret: Option<SelectionRange> = None;
+    for loc in locations {
+        if let Some(parent) = ret.as_ref() {
+            let parent_range = parent.range;
+            let range = location_to_lsp_range(&loc);
+            if range.start < parent_range.start || range.end > parent_range.end {
+                continue;
+            }
+        }
+        ret = Some(SelectionRange {
+            range: location_to_lsp_range(&loc),
+            parent: ret.map(Box::new),
+        });
+    }
+    ret.unwrap_or_else(|| SelectionRange {
+        range: location_to_lsp_range(&(pos..pos)),
+        parent: None,
+    })
+}
+
+fn tag_locations(tag_location: &TagLocation) -> [Location; 2] {
+    let start_tag = tag_location.start.0.start..tag_location.start.1.end;
+    let whole = match tag_location.end.as_ref() {
+        None => start_tag.clone(),
+        Some((_, x)) => start_tag.start..x.end,
+    };
+    [whole, start_tag]
+}
+
+fn collect_wxml_locations(
+    template: &Template,
+    content: &FileContentMetadata,
+    pos: Position,
+) -> Vec<Location> {
+    let mut ret = vec![];
+    let src = &content.content;
+    let char_at = |pos: Position| {
+        let idx = content.content_index_for_line_utf16_col(pos.line, pos.utf16_col);
+        src[idx..].chars().next()
+    };
+    let char_before = |pos: Position| {
+        let idx = content.content_index_for_line_utf16_col(pos.line, pos.utf16_col);
+        src[..idx].chars().next_back()
+    };
+
+    // tags outside the template tree
+    for i in template.globals.imports.iter() {
+        ret.extend(tag_locations(&i.tag_location));
+    }
+    for i in template.globals.includes.iter() {
+        ret.extend(tag_locations(&i.tag_location));
+    }
+    for i in template.globals.scripts.iter() {
+        ret.extend(tag_locations(&i.tag_location()));
+        if let Script::Inline {
+            content_location, ..
+        } = i
+        {
+            ret.push(content_location.clone());
+        }
+    }
+    for i in template.globals.sub_templates.iter() {
+        ret.extend(tag_locations(&i.tag_location));
+    }
+
+    // elements and their attributes
+    for_each_template_element(template, |elem, _| {
+        let loc = elem.location();
+        if !inclusive_contains(&loc, pos) {
+            return;
+        }
+        ret.push(loc);
+        let [_, start_tag] = tag_locations(&elem.tag_location);
+        if !inclusive_contains(&start_tag, pos) {
+            return;
+        }
+        ret.push(start_tag);
+        for_each_attribute_in_element(elem, |name_loc, value_loc| {
+            let Some(value_loc) = value_loc else {
+                ret.push(name_loc);
+                return;
+            };
+            let quote = char_at(value_loc.end).filter(|x| *x == '"' || *x == '\'');
+            let quoted_value_loc = if quote.is_some() && char_before(value_loc.start) == quote {
+                let start = Position {
+                    line: value_loc.start.line,
+                    utf16_col: value_loc.start.utf16_col.saturating_sub(1),
+                };
+                let end = Position {
+                    line: value_loc.end.line,
+                    utf16_col: value_loc.end.utf16_col + 1,
+                };
+                start..end
+            } else {
+                value_loc.clone()
+            };
+            if name_loc.start < quoted_value_loc.end {
+                ret.push(name_loc.start..quoted_value_loc.end);
+            }
+            ret.push(quoted_value_loc);
+            ret.push(value_loc);
+        });
+    });
+
+    // values and expressions
+    for_each_template_root(template, |node, scopes| {
+        if let Node::Text(value) = node {
+            ret.push(value.location());
+        }
+        for_each_template_value_in_subtree(node, &mut scopes.clone(), |value, _| {
+            ret.push(value.location());
+        });
+        for_each_template_expression_in_subtree(node, &mut scopes.clone(), |expr, _| {
+            ret.push(expr.location());
+        });
+    });
+
+    ret
+}
+
+fn collect_wxss_locations(sheet: &StyleSheet, pos: Position) -> Vec<Location> {
+    fn rec_token_tree(tt: &TokenTree, pos: Position, ret: &mut Vec<Location>) {
+        let loc = tt.location();
+        if !inclusive_contains(&loc, pos) {
+            return;
+        }
+        ret.push(loc);
+        if let Some(children) = tt.children() {
+            if let (Some(first), Some(last)) = (children.first(), children.last()) {
+                ret.push(first.location().start..last.location().end);
+            }
+            for child in children.iter() {
+                rec_token_tree(child, pos, ret);
+            }
+        }
+    }
+    fn rec_brace<T>(
+        x: &Option<BraceOrSemicolon<T>>,
+        pos: Position,
+        ret: &mut Vec<Location>,
+        f: impl FnOnce(&T, &mut Vec<Location>),
+    ) {
+        if let Some(BraceOrSemicolon::Brace(x)) = x {
+            let loc = x.location();
+            if inclusive_contains(&loc, pos) {
+                ret.push(loc);
+                ret.push(x.left.end..x.right.start);
+                f(&x.children, ret);
+            }
+        }
+    }
+    fn rec_properties(list: &List<RuleOrProperty>, pos: Position, ret: &mut Vec<Location>) {
+        for item in list.iter() {
+            match item {
+                RuleOrProperty::Rule(rule) => rec_rule(rule, pos, ret),
+                RuleOrProperty::Property(prop) => {
+                    let loc = prop.location();
+                    if !inclusive_contains(&loc, pos) {
+                        continue;
+                    }
+                    ret.push(loc);
+                    ret.push(prop.name.location.clone());
+                    if let (Some(first), Some(last)) = (prop.value.first(), prop.value.last()) {
+                        ret.push(first.location().start..last.location().end);
+                    }
+                    for tt in prop.value.iter() {
+                        rec_token_tree(tt, pos, ret);
+                    }
+                }
+            }
+        }
+    }
+    fn rec_rule(rule: &Rule, pos: Position, ret: &mut Vec<Location>) {
+        let loc = rule.location();
+        if !inclusive_contains(&loc, pos) {
+            return;
+        }
+        ret.push(loc);
+        match rule {
+            Rule::Unknown(list) => {
+                for tt in list.iter() {
+                    rec_token_tree(tt, pos, ret);
+                }
+            }
+            Rule::Style(x) => {
+                ret.push(x.selector.location());
+                for list in x.selector.iter_values() {
+                    ret.push(list.location());
+                    for sel in list.iter() {
+                        ret.push(sel.location());
+                    }
+                }
+                rec_brace(&x.brace, pos, ret, |x, ret| rec_properties(x, pos, ret));
+            }
+            Rule::Import(x) => {
+                ret.extend(x.url.location());
+            }
+            Rule::Media(x) => {
+                if let Some(list) = x.list.as_ref() {
+                    ret.push(list.location());
+                }
+                rec_brace(&x.body, pos, ret, |x, ret| {
+                    for rule in x.iter() {
+                        rec_rule(rule, pos, ret);
+                    }
+                });
+            }
+            Rule::FontFace(x) => {
+                rec_brace(&x.body, pos, ret, |x, ret| rec_properties(x, pos, ret));
+            }
+            Rule::Keyframes(x) => {
+                ret.extend(x.name.location());
+                rec_brace(&x.body, pos, ret, |x, ret| {
+                    for keyframe in x.iter() {
+                        let loc = keyframe.location();
+                        if !inclusive_contains(&loc, pos) {
+                            continue;
+                        }
+                        ret.push(loc);
+                        match keyframe {
+                            Keyframe::Named { progress, body } => {
+                                ret.extend(progress.location());
+                                rec_brace(body, pos, ret, |x, ret| rec_properties(x, pos, ret));
+                            }
+                            Keyframe::Percentage { progress, body } => {
+                                ret.extend(progress.location());
+                                rec_brace(body, pos, ret, |x, ret| rec_properties(x, pos, ret));
+                            }
+                            Keyframe::Unknown(list) => {
+                                for tt in list.iter() {
+                                    rec_token_tree(tt, pos, ret);
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+            Rule::UnknownAtRule(at_keyword, list) => {
+                ret.push(at_keyword.location.clone());
+                for tt in list.iter() {
+                    rec_token_tree(tt, pos, ret);
+                }
+            }
+        }
+    }
+    let mut ret = vec![];
+    for rule in sheet.items.iter() {
+        rec_rule(rule, pos, &mut ret);
+    }
+    ret
+}
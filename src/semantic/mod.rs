@@ -1,13 +1,17 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, path::Path};
 
 use glass_easel_template_compiler::parse::Position;
 use lsp_types::{
-    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensParams,
-    SemanticTokensRangeParams,
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensDelta,
+    SemanticTokensDeltaParams, SemanticTokensEdit, SemanticTokensFullDeltaResult,
+    SemanticTokensParams, SemanticTokensRangeParams,
 };
 
 use crate::{
-    context::{project::FileContentMetadata, FileLang},
+    context::{
+        project::{FileContentMetadata, Project},
+        FileLang,
+    },
     ServerContext,
 };
 
@@ -66,6 +70,35 @@ enum TokenModifier {
     Deprecated = 0x00000004,
 }
 
+fn find_full_tokens(
+    project: &Project,
+    abs_path: &Path,
+    file_lang: FileLang,
+) -> anyhow::Result<Vec<SemanticToken>> {
+    let Some(content) = project.cached_file_content(abs_path) else {
+        return Ok(vec![]);
+    };
+    let range = Position {
+        line: 0,
+        utf16_col: 0,
+    }..Position {
+        line: u32::MAX,
+        utf16_col: u32::MAX,
+    };
+    let data = match file_lang {
+        FileLang::Wxml => {
+            let template = project.get_wxml_tree(abs_path)?;
+            wxml::find_wxml_semantic_tokens(content, template, range)
+        }
+        FileLang::Wxss => {
+            let sheet = project.get_style_sheet(abs_path, false)?;
+            wxss::find_wxss_semantic_tokens(content, sheet, range)
+        }
+        _ => vec![],
+    };
+    Ok(data)
+}
+
 pub(crate) async fn tokens_full(
     ctx: ServerContext,
     params: SemanticTokensParams,
@@ -75,37 +108,10 @@ pub(crate) async fn tokens_full(
         .project_thread_task(
             &params.text_document.uri,
             move |project, abs_path, file_lang| -> anyhow::Result<_> {
-                let data = if let Some(content) = project.cached_file_content(&abs_path) {
-                    match file_lang {
-                        FileLang::Wxml => {
-                            let template = project.get_wxml_tree(&abs_path)?;
-                            let range = Position {
-                                line: 0,
-                                utf16_col: 0,
-                            }..Position {
-                                line: u32::MAX,
-                                utf16_col: u32::MAX,
-                            };
-                            wxml::find_wxml_semantic_tokens(content, template, range)
-                        }
-                        FileLang::Wxss => {
-                            let sheet = project.get_style_sheet(&abs_path, false)?;
-                            let range = Position {
-                                line: 0,
-                                utf16_col: 0,
-                            }..Position {
-                                line: u32::MAX,
-                                utf16_col: u32::MAX,
-                            };
-                            wxss::find_wxss_semantic_tokens(content, sheet, range)
-                        }
-                        _ => vec![],
-                    }
-                } else {
-                    vec![]
-                };
+                let data = find_full_tokens(project, &abs_path, file_lang)?;
+                let result_id = project.cache_semantic_tokens(&abs_path, data.clone());
                 Ok(SemanticTokens {
-                    result_id: None,
+                    result_id: Some(result_id),
                     data,
                 })
             },
@@ -114,6 +120,70 @@ pub(crate) async fn tokens_full(
     Ok(ret)
 }
 
+pub(crate) async fn tokens_full_delta(
+    ctx: ServerContext,
+    params: SemanticTokensDeltaParams,
+) -> anyhow::Result<SemanticTokensFullDeltaResult> {
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<_> {
+                let data = find_full_tokens(project, &abs_path, file_lang)?;
+                let edits = project
+                    .cached_semantic_tokens(&abs_path, &params.previous_result_id)
+                    .map(|old| semantic_tokens_edits(old, &data));
+                let result_id = project.cache_semantic_tokens(&abs_path, data.clone());
+                let ret = match edits {
+                    Some(edits) => {
+                        SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                            result_id: Some(result_id),
+                            edits,
+                        })
+                    }
+                    // the previous result is unknown, so send all tokens
+                    None => SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+                        result_id: Some(result_id),
+                        data,
+                    }),
+                };
+                Ok(ret)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+/// Generate the edits which turn the `old` tokens into the `new` ones.
+///
+/// The tokens are position-relative, so an edit of the document usually changes a continuous range of them.
+/// A single edit replacing the tokens between the common prefix and the common suffix is generated.
+fn semantic_tokens_edits(old: &[SemanticToken], new: &[SemanticToken]) -> Vec<SemanticTokensEdit> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let deleted = &old[prefix..old.len() - suffix];
+    let inserted = &new[prefix..new.len() - suffix];
+    if deleted.is_empty() && inserted.is_empty() {
+        return vec![];
+    }
+
+    // the edit offsets are counted in integers, and each token has 5 integers
+    vec![SemanticTokensEdit {
+        start: prefix as u32 * 5,
+        delete_count: deleted.len() as u32 * 5,
+        data: Some(inserted.to_vec()),
+    }]
+}
+
 pub(crate) async fn tokens_range(
     ctx: ServerContext,
     params: SemanticTokensRangeParams,
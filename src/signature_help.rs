@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use glass_easel_template_compiler::parse::tag::Script;
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+
+use crate::{
+    context::{project::Project, FileLang},
+    utils::{ensure_file_extension, inclusive_contains},
+    wxs::collect_exported_functions,
+    wxss::{token::TokenTree, CSSParse, Position},
+    wxss_utils::for_each_property_in_style_sheet,
+    BackendConfig, ServerContext,
+};
+
+pub(crate) async fn signature_help(
+    ctx: ServerContext,
+    params: SignatureHelpParams,
+) -> anyhow::Result<Option<SignatureHelp>> {
+    let backend_config = ctx.backend_config();
+    let pos = params.text_document_position_params.position;
+    let pos = Position {
+        line: pos.line,
+        utf16_col: pos.character,
+    };
+    let ret = ctx
+        .clone()
+        .project_thread_task(
+            &params.text_document_position_params.text_document.uri,
+            move |project, abs_path, file_lang| -> anyhow::Result<Option<SignatureHelp>> {
+                let ret = match file_lang {
+                    FileLang::Wxml => wxml_signature_help(project, &abs_path, pos),
+                    FileLang::Wxss | FileLang::OtherSs => {
+                        wxss_signature_help(project, &backend_config, &abs_path, pos)
+                    }
+                    _ => None,
+                };
+                Ok(ret)
+            },
+        )
+        .await??;
+    Ok(ret)
+}
+
+fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+/// Build a single signature like `name(a, b)` with the parameter label offsets.
+fn build_signature_help(
+    name: &str,
+    params: Vec<(String, Option<Documentation>)>,
+    documentation: Option<Documentation>,
+    active_parameter: u32,
+) -> SignatureHelp {
+    let mut label = format!("{}(", name);
+    let mut parameters = vec![];
+    for (i, (param, documentation)) in params.into_iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+        // label offsets are counted in UTF-16 code units
+        let start = label.encode_utf16().count() as u32;
+        let end = start + param.encode_utf16().count() as u32;
+        label.push_str(&param);
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation,
+        });
+    }
+    label.push(')');
+    let active_parameter = match parameters.len() {
+        0 => None,
+        len => Some(active_parameter.min(len as u32 - 1)),
+    };
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    }
+}
+
+/// Find the template expression which is not closed before the end of the source.
+///
+/// The braces and the string literals in the expressions are balanced,
+/// so that the `}}` in `{{ f({ a: { b: 1 }}) }}` does not end the expression.
+fn unclosed_template_expression(src: &str) -> Option<&str> {
+    let mut expr_start: Option<usize> = None;
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut chars = src.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if expr_start.is_none() {
+            if c == '{' && chars.next_if(|x| x.1 == '{').is_some() {
+                expr_start = Some(i + 2);
+                depth = 0;
+                quote = None;
+                escaped = false;
+            }
+            continue;
+        }
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => quote = Some(c),
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            '}' if chars.next_if(|x| x.1 == '}').is_some() => expr_start = None,
+            _ => {}
+        }
+    }
+    expr_start.map(|start| &src[start..])
+}
+
+/// Find the innermost `module.func(` call which is not closed before the end of the source.
+///
+/// The source should end at the cursor.
+/// Returns the module name, the function name and the index of the argument being typed.
+fn find_wxs_call(src: &str) -> Option<(&str, &str, u32)> {
+    let expr = unclosed_template_expression(src)?;
+
+    // track the open brackets and the commas directly in them
+    let mut stack: Vec<(char, usize, u32)> = vec![];
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in expr.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => quote = Some(c),
+            '(' | '[' | '{' => stack.push((c, i, 0)),
+            ')' | ']' | '}' => {
+                stack.pop();
+            }
+            ',' => {
+                if let Some(x) = stack.last_mut() {
+                    x.2 += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    let (_, paren_idx, commas) = stack.iter().rev().find(|x| x.0 == '(')?;
+
+    // the callee should be a `module.func` member expression
+    let callee = expr[..*paren_idx].trim_end();
+    let callee_start = callee
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '$' || *c == '.'))
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    let (module_name, func_name) = callee[callee_start..].split_once('.')?;
+    let is_ident =
+        |x: &str| x.chars().next().is_some_and(|c| !c.is_ascii_digit()) && !x.contains('.');
+    if !is_ident(module_name) || !is_ident(func_name) {
+        return None;
+    }
+    Some((module_name, func_name, *commas))
+}
+
+fn wxml_signature_help(project: &Project, abs_path: &Path, pos: Position) -> Option<SignatureHelp> {
+    let content = project.cached_file_content(abs_path)?;
+    let template = project.get_wxml_tree(abs_path).ok()?;
+    let src = &content.content;
+    let index = content.content_index_for_line_utf16_col(pos.line, pos.utf16_col);
+    let (module_name, func_name, active_parameter) = find_wxs_call(&src[..index])?;
+    let script = template
+        .globals
+        .scripts
+        .iter()
+        .find(|x| x.module_name().name.as_str() == module_name)?;
+    let script_src = match script {
+        Script::Inline { content, .. } => content.to_string(),
+        Script::GlobalRef { src, .. } => {
+            let p = project.find_rel_path_for_file(abs_path, &src.name)?;
+            let p = ensure_file_extension(&p, "wxs")?;
+            match project.cached_file_content(&p) {
+                Some(x) => x.content.clone(),
+                None => std::fs::read_to_string(&p).ok()?,
+            }
+        }
+        _ => return None,
+    };
+    let func = collect_exported_functions(&script_src)
+        .into_iter()
+        .find(|x| x.name == func_name)?;
+    let params = func.params.into_iter().map(|x| (x, None)).collect();
+    let name = format!("{}.{}", module_name, func_name);
+    Some(build_signature_help(&name, params, None, active_parameter))
+}
+
+fn wxss_signature_help(
+    project: &Project,
+    backend_config: &BackendConfig,
+    abs_path: &Path,
+    pos: Position,
+) -> Option<SignatureHelp> {
+    fn rec(tt: &TokenTree, pos: Position, ret: &mut Option<(String, u32)>) {
+        let Some(children) = tt.children() else {
+            return;
+        };
+        if !inclusive_contains(&tt.location(), pos) {
+            return;
+        }
+        if let TokenTree::Function(x) = tt {
+            if pos >= x.left.end && (x.right.start == x.right.end || pos <= x.right.start) {
+                let commas = x
+                    .children
+                    .iter()
+                    .filter(|x| matches!(x, TokenTree::Comma(c) if c.location().end <= pos))
+                    .count();
+                *ret = Some((x.name.to_string(), commas as u32));
+            }
+        }
+        for child in children.iter() {
+            rec(child, pos, ret);
+        }
+    }
+    let sheet = project.get_style_sheet(abs_path, false).ok()?;
+    let mut found = None;
+    for_each_property_in_style_sheet(sheet, |prop| {
+        for tt in prop.value.iter() {
+            rec(tt, pos, &mut found);
+        }
+    });
+    let (name, active_parameter) = found?;
+    let func = backend_config
+        .style_function
+        .iter()
+        .find(|x| x.name.eq_ignore_ascii_case(&name))?;
+    let params = func
+        .parameter
+        .iter()
+        .map(|x| {
+            let documentation =
+                (!x.description.is_empty()).then(|| markdown(x.description.clone()));
+            (x.name.clone(), documentation)
+        })
+        .collect();
+    let mut doc = func.description.clone();
+    if let Some(reference) = func.reference.as_ref() {
+        doc.push_str(&format!("\n\n[Reference]({})", reference));
+    }
+    let documentation = (!doc.trim().is_empty()).then(|| markdown(doc));
+    Some(build_signature_help(
+        &func.name,
+        params,
+        documentation,
+        active_parameter,
+    ))
+}
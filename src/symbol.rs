@@ -1,16 +1,26 @@
+use std::path::Path;
+
 use glass_easel_template_compiler::parse::{Template, TemplateStructure};
-use lsp_types::{DocumentSymbol, DocumentSymbolParams, SymbolKind};
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, Location, SymbolInformation, SymbolKind, Url,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
 
 use crate::{
-    context::FileLang,
-    utils::location_to_lsp_range,
+    context::{project::Project, FileLang},
+    json_utils::for_each_json_entry,
+    utils::{fuzzy_match, location_to_lsp_range},
     wxss::{
-        keyframe::Keyframe, token::BraceOrSemicolon, CSSParse, List, Rule, RuleOrProperty,
-        StyleSheet,
+        keyframe::Keyframe, rule::Selector, token::BraceOrSemicolon, CSSParse, List, Rule,
+        RuleOrProperty, StyleSheet,
     },
+    wxss_utils::{for_each_rule_in_style_sheet, for_each_selector_in_style_sheet},
     ServerContext,
 };
 
+/// The max number of symbols returned in `workspace/symbol`.
+const WORKSPACE_SYMBOL_LIMIT: usize = 256;
+
 pub(crate) async fn document_symbol(
     ctx: ServerContext,
     params: DocumentSymbolParams,
@@ -260,3 +270,137 @@ fn collect_wxss_symbol_list(sheet: &StyleSheet) -> Vec<DocumentSymbol> {
     }
     sheet.items.iter().filter_map(rec).collect()
 }
+
+pub(crate) async fn workspace_symbol(
+    ctx: ServerContext,
+    params: WorkspaceSymbolParams,
+) -> anyhow::Result<Option<WorkspaceSymbolResponse>> {
+    let query = params.query;
+    let mut list: Vec<_> = ctx
+        .all_project_thread_tasks(move |project| collect_workspace_symbol_list(project, &query))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    // the files are not listed in a stable order, so the locations are also compared
+    list.sort_by(|(a_score, a), (b_score, b)| {
+        let pos = |x: &SymbolInformation| {
+            (
+                x.location.range.start.line,
+                x.location.range.start.character,
+            )
+        };
+        b_score
+            .cmp(a_score)
+            .then(a.name.cmp(&b.name))
+            .then(a.location.uri.cmp(&b.location.uri))
+            .then(pos(a).cmp(&pos(b)))
+    });
+    list.truncate(WORKSPACE_SYMBOL_LIMIT);
+    Ok(Some(WorkspaceSymbolResponse::Flat(
+        list.into_iter().map(|(_, x)| x).collect(),
+    )))
+}
+
+/// Collect the template names, the custom component tags, the WXSS class and ID selectors,
+/// and the keyframes names matching the query, with the matching scores.
+fn collect_workspace_symbol_list(project: &Project, query: &str) -> Vec<(i32, SymbolInformation)> {
+    let mut ret = vec![];
+    let mut add = |name: String, kind: SymbolKind, abs_path: &Path, range: lsp_types::Range| {
+        let Some(score) = fuzzy_match(query, &name) else {
+            return;
+        };
+        let Ok(uri) = Url::from_file_path(abs_path) else {
+            return;
+        };
+        let container_name = project
+            .root()
+            .and_then(|root| abs_path.strip_prefix(root).ok())
+            .unwrap_or(abs_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        #[allow(deprecated)]
+        ret.push((
+            score,
+            SymbolInformation {
+                name,
+                kind,
+                tags: None,
+                deprecated: None,
+                location: Location { uri, range },
+                container_name: Some(container_name),
+            },
+        ));
+    };
+    for (abs_path, file_lang) in project.list_file_paths() {
+        match file_lang {
+            FileLang::Wxml => {
+                let Ok(template) = project.get_wxml_tree(abs_path) else {
+                    continue;
+                };
+                for sub in &template.globals.sub_templates {
+                    if sub.name.is("") {
+                        continue;
+                    }
+                    add(
+                        sub.name.name.to_string(),
+                        SymbolKind::NAMESPACE,
+                        abs_path,
+                        location_to_lsp_range(&sub.name.location()),
+                    );
+                }
+            }
+            FileLang::Json => {
+                let Some(content) = project.cached_file_content(abs_path) else {
+                    continue;
+                };
+                for_each_json_entry(content, |path, loc| {
+                    if let [field, key] = path {
+                        if field == "usingComponents" {
+                            add(
+                                key.to_string(),
+                                SymbolKind::CLASS,
+                                abs_path,
+                                location_to_lsp_range(&loc.key),
+                            );
+                        }
+                    }
+                });
+            }
+            FileLang::Wxss | FileLang::OtherSs => {
+                let Ok(sheet) = project.get_style_sheet(abs_path, false) else {
+                    continue;
+                };
+                for_each_selector_in_style_sheet(sheet, |sel| match sel {
+                    Selector::Class(op, x) => add(
+                        format!(".{}", x.content),
+                        SymbolKind::CLASS,
+                        abs_path,
+                        location_to_lsp_range(&(op.location.start..x.location.end)),
+                    ),
+                    Selector::Id(x) => add(
+                        format!("#{}", x.content),
+                        SymbolKind::CLASS,
+                        abs_path,
+                        location_to_lsp_range(&x.location),
+                    ),
+                    _ => {}
+                });
+                for_each_rule_in_style_sheet(sheet, |rule| {
+                    if let Rule::Keyframes(x) = rule {
+                        if let Some(name) = x.name.known() {
+                            add(
+                                format!("@keyframes {}", name.content),
+                                SymbolKind::MODULE,
+                                abs_path,
+                                location_to_lsp_range(&name.location),
+                            );
+                        }
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+    ret
+}
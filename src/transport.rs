@@ -0,0 +1,137 @@
+//! Transports of the LSP connection.
+
+use std::{
+    io::{BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+};
+
+use lsp_server::{Connection, Message};
+
+// The transport options for the language server, stdio is used if none is specified.
+//
+// This is not a doc comment, otherwise it overrides the `about` text of the flattening command.
+#[derive(Debug, Clone, Default, clap::Args)]
+#[group(multiple = false)]
+pub struct TransportArgs {
+    /// Listen on a TCP port (or `host:port`), serving clients one after another
+    #[arg(long, value_name = "PORT")]
+    listen: Option<String>,
+    /// Connect to a client listening on `host:port`
+    #[arg(long, value_name = "HOST:PORT")]
+    connect: Option<String>,
+    /// Listen on a Unix domain socket, serving clients one after another
+    #[cfg(unix)]
+    #[arg(long, value_name = "PATH")]
+    unix_listen: Option<std::path::PathBuf>,
+    /// Connect to a client listening on a Unix domain socket
+    #[cfg(unix)]
+    #[arg(long, value_name = "PATH")]
+    unix_connect: Option<std::path::PathBuf>,
+}
+
+/// Create a connection which reads messages from `reader` and writes messages to `writer`.
+fn stream_connection(
+    reader: impl Read + Send + 'static,
+    mut writer: impl Write + Send + 'static,
+) -> Connection {
+    let (server, client) = Connection::memory();
+    let Connection { sender, receiver } = client;
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        loop {
+            let msg = match Message::read(&mut reader) {
+                Ok(Some(x)) => x,
+                Ok(None) => break,
+                Err(err) => {
+                    log::error!("Failed to read LSP message: {}", err);
+                    break;
+                }
+            };
+            let is_exit = matches!(&msg, Message::Notification(x) if x.method == "exit");
+            if sender.send(msg).is_err() || is_exit {
+                break;
+            }
+        }
+    });
+    std::thread::spawn(move || {
+        let mut broken = false;
+        for msg in receiver {
+            if broken {
+                continue;
+            }
+            if let Err(err) = msg.write(&mut writer) {
+                log::error!("Failed to write LSP message: {}", err);
+                broken = true;
+            }
+        }
+    });
+    server
+}
+
+fn tcp_connection(stream: TcpStream) -> anyhow::Result<Connection> {
+    Ok(stream_connection(stream.try_clone()?, stream))
+}
+
+#[cfg(unix)]
+fn unix_connection(stream: std::os::unix::net::UnixStream) -> anyhow::Result<Connection> {
+    Ok(stream_connection(stream.try_clone()?, stream))
+}
+
+fn tcp_listen_addr(s: &str) -> String {
+    match s.parse::<u16>() {
+        Ok(port) => format!("127.0.0.1:{}", port),
+        Err(_) => s.to_string(),
+    }
+}
+
+/// Run `serve` for each connection of the transport.
+///
+/// For the listening transports, clients are accepted and served one after another.
+pub(crate) async fn serve_with<F>(
+    args: TransportArgs,
+    serve: impl Fn(Connection) -> F,
+) -> anyhow::Result<()>
+where
+    F: std::future::Future<Output = anyhow::Result<()>>,
+{
+    if let Some(addr) = args.listen.as_ref() {
+        let addr = tcp_listen_addr(addr);
+        let listener = Arc::new(TcpListener::bind(&addr)?);
+        eprintln!("listening on {}", listener.local_addr()?);
+        loop {
+            let listener = listener.clone();
+            let (stream, peer) = tokio::task::spawn_blocking(move || listener.accept()).await??;
+            eprintln!("accepted connection from {}", peer);
+            if let Err(err) = serve(tcp_connection(stream)?).await {
+                eprintln!("{}", err);
+            }
+        }
+    }
+    if let Some(addr) = args.connect.as_ref() {
+        let stream = TcpStream::connect(addr)?;
+        return serve(tcp_connection(stream)?).await;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::net::{UnixListener, UnixStream};
+        if let Some(path) = args.unix_listen.as_ref() {
+            let listener = Arc::new(UnixListener::bind(path)?);
+            eprintln!("listening on {:?}", path);
+            loop {
+                let listener = listener.clone();
+                let (stream, _) = tokio::task::spawn_blocking(move || listener.accept()).await??;
+                eprintln!("accepted connection");
+                if let Err(err) = serve(unix_connection(stream)?).await {
+                    eprintln!("{}", err);
+                }
+            }
+        }
+        if let Some(path) = args.unix_connect.as_ref() {
+            let stream = UnixStream::connect(path)?;
+            return serve(unix_connection(stream)?).await;
+        }
+    }
+    let (connection, _io_threads) = Connection::stdio();
+    serve(connection).await
+}
@@ -30,6 +30,19 @@ pub(crate) fn dash_to_camel(s: &str) -> String {
     camel_name
 }
 
+pub(crate) fn camel_to_dash(s: &str) -> String {
+    let mut dash_name = String::new();
+    for c in s.chars() {
+        if c.is_ascii_uppercase() {
+            dash_name.push('-');
+            dash_name.push(c.to_ascii_lowercase());
+        } else {
+            dash_name.push(c);
+        }
+    }
+    dash_name
+}
+
 pub(crate) fn generate_non_fs_fake_path(uri: &Url) -> PathBuf {
     let mut p = PathBuf::from("/");
     p.push(uri.scheme());
@@ -142,3 +155,36 @@ pub(crate) fn exclusive_ordering(loc: &Location, pos: Position) -> Ordering {
         Ordering::Equal
     }
 }
+
+/// Match `query` as a case-insensitive subsequence of `name`.
+///
+/// Returns a score (higher is better) if matched.
+/// Consecutive matches and matches at word starts are preferred.
+pub(crate) fn fuzzy_match(query: &str, name: &str) -> Option<i32> {
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut i = 0;
+    let mut last_matched: Option<usize> = None;
+    for q in query.chars().filter(|x| !x.is_whitespace()) {
+        loop {
+            let c = *name.get(i)?;
+            if c == q || c.to_lowercase().eq(q.to_lowercase()) {
+                break;
+            }
+            i += 1;
+        }
+        score += 1;
+        if i > 0 && last_matched == Some(i - 1) {
+            score += 4;
+        }
+        if i == 0
+            || !name[i - 1].is_alphanumeric()
+            || (name[i - 1].is_lowercase() && name[i].is_uppercase())
+        {
+            score += 2;
+        }
+        last_matched = Some(i);
+        i += 1;
+    }
+    Some(score * 64 - (name.len() as i32).min(63))
+}
@@ -138,7 +138,10 @@ pub(crate) enum Token<'a> {
     StaticStylePropertyValue(Range<Position>, &'a str, &'a Ident, &'a Element),
     StaticStyleValuePart(TokenStaticStyleValuePart, &'a Element),
     EventHandler(&'a StrName, &'a Ident),
+    StaticEventHandler(Range<Position>, &'a str, &'a Element),
+    GenericName(&'a Ident, &'a Element),
     GenericRef(&'a StrName, &'a Ident),
+    SlotName(Range<Position>, &'a str, &'a Element),
     SlotValueDefinition(&'a Ident),
     SlotValueRef(&'a Ident, &'a StrName, &'a Element),
     SlotValueScope(&'a StrName, &'a Ident, &'a Element),
@@ -339,8 +342,11 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
             None
         }
     }
+    // `slot_owner` is the element which the `slot` attributes of the nodes refer to,
+    // i.e. the nearest ancestor which is not a `wx:if`, `wx:for` or `<block>`
     fn find_in_nodes<'a>(
         parent: Option<&'a Element>,
+        slot_owner: Option<&'a Element>,
         nodes: &'a [Node],
         pos: Position,
         scopes: &[ScopeKind<'a>],
@@ -416,6 +422,7 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                         }
                         fn find_in_common<'a>(
                             parent: Option<&'a Element>,
+                            slot_owner: Option<&'a Element>,
                             elem: &'a Element,
                             common: &'a CommonElementAttributes,
                             pos: Position,
@@ -443,6 +450,11 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                     return Token::AttributeKeyword(loc.clone(), &elem);
                                 }
                                 if let Some(ret) = find_in_value(v, pos, scopes) {
+                                    if let (Token::StaticValuePart(loc, v), Some(owner)) =
+                                        (&ret, slot_owner)
+                                    {
+                                        return Token::SlotName(loc.clone(), v, owner);
+                                    }
                                     return ret;
                                 }
                             }
@@ -467,6 +479,9 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                     return Token::EventName(&ev.name, elem);
                                 }
                                 if let Some(ret) = find_in_option_value(&ev.value, pos, scopes) {
+                                    if let Token::StaticValuePart(loc, v) = ret {
+                                        return Token::StaticEventHandler(loc, v, elem);
+                                    }
                                     return ret;
                                 }
                             }
@@ -534,7 +549,7 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                     }
                                     for attr in generics.iter() {
                                         if ident_contains(&attr.name, pos) {
-                                            return Token::AttributeName(&attr.name, elem);
+                                            return Token::GenericName(&attr.name, elem);
                                         }
                                         if str_name_contains(&attr.value, pos) {
                                             return Token::GenericRef(&attr.value, tag_name);
@@ -624,13 +639,21 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                         }
                                         _ => {}
                                     }
-                                    return find_in_common(parent, elem, common, pos, scopes)
-                                        .or(Token::StartTagBody(elem));
+                                    return find_in_common(
+                                        parent, slot_owner, elem, common, pos, scopes,
+                                    )
+                                    .or(Token::StartTagBody(elem));
                                 }
                                 if end_tag_body_contains(&elem.tag_location, pos) {
                                     return Token::EndTagBody(elem);
                                 }
-                                return find_in_nodes(Some(elem), &children, pos, scopes);
+                                return find_in_nodes(
+                                    Some(elem),
+                                    Some(elem),
+                                    children,
+                                    pos,
+                                    scopes,
+                                );
                             }
                             ElementKind::Pure {
                                 children,
@@ -645,6 +668,11 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                             return Token::OtherKeyword(loc.clone());
                                         }
                                         if let Some(ret) = find_in_value(v, pos, scopes) {
+                                            if let (Token::StaticValuePart(loc, v), Some(owner)) =
+                                                (&ret, slot_owner)
+                                            {
+                                                return Token::SlotName(loc.clone(), v, owner);
+                                            }
                                             return ret;
                                         }
                                     }
@@ -654,7 +682,13 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                 if end_tag_body_contains(&elem.tag_location, pos) {
                                     return Token::EndTagBody(elem);
                                 }
-                                return find_in_nodes(Some(elem), &children, pos, scopes);
+                                return find_in_nodes(
+                                    Some(elem),
+                                    slot_owner,
+                                    children,
+                                    pos,
+                                    scopes,
+                                );
                             }
                             ElementKind::If {
                                 branches,
@@ -668,7 +702,8 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                     if let Some(ret) = find_in_value(v, pos, scopes) {
                                         return ret;
                                     }
-                                    let ret = find_in_nodes(Some(elem), nodes, pos, scopes);
+                                    let ret =
+                                        find_in_nodes(Some(elem), slot_owner, nodes, pos, scopes);
                                     if let Token::None = ret {
                                         continue;
                                     }
@@ -678,7 +713,8 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                     if inclusive_contains(loc, pos) {
                                         return Token::AttributeKeyword(loc.clone(), &elem);
                                     }
-                                    let ret = find_in_nodes(Some(elem), nodes, pos, scopes);
+                                    let ret =
+                                        find_in_nodes(Some(elem), slot_owner, nodes, pos, scopes);
                                     if let Token::None = ret {
                                         // empty
                                     } else {
@@ -733,7 +769,8 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                 }
                                 scopes.push(ScopeKind::ForScope(&item_name.1, elem));
                                 scopes.push(ScopeKind::ForScope(&index_name.1, elem));
-                                let ret = find_in_nodes(Some(elem), &children, pos, scopes);
+                                let ret =
+                                    find_in_nodes(Some(elem), slot_owner, children, pos, scopes);
                                 if let Token::None = ret {
                                     if start_tag_body_contains(&elem.tag_location, pos) {
                                         return Token::StartTagBody(elem);
@@ -808,8 +845,10 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
                                             return ret;
                                         }
                                     }
-                                    return find_in_common(parent, elem, common, pos, scopes)
-                                        .or(Token::StartTagBody(elem));
+                                    return find_in_common(
+                                        parent, slot_owner, elem, common, pos, scopes,
+                                    )
+                                    .or(Token::StartTagBody(elem));
                                 }
                                 if end_tag_body_contains(&elem.tag_location, pos) {
                                     return Token::EndTagBody(elem);
@@ -915,11 +954,11 @@ pub(crate) fn find_token_in_position<'a>(template: &'a Template, pos: Position)
             if str_name_contains(&i.name, pos) {
                 return Token::TemplateName(&i.name);
             }
-            return find_in_nodes(None, &i.content, pos, &scopes);
+            return find_in_nodes(None, None, &i.content, pos, &scopes);
         }
     }
 
-    find_in_nodes(None, &template.content, pos, &scopes)
+    find_in_nodes(None, None, &template.content, pos, &scopes)
 }
 
 pub(crate) fn for_each_template_root<'a>(
@@ -1247,7 +1286,7 @@ pub(crate) fn for_each_scope_ref<'a>(
 pub(crate) fn for_each_slot_in_subtree<'a>(
     node: &'a Node,
     scopes: &mut Vec<ScopeKind<'a>>,
-    mut f: impl FnMut(&Element),
+    mut f: impl FnMut(&'a Element),
 ) {
     for_each_template_element_in_subtree(node, scopes, &mut |elem, _scopes| match &elem.kind {
         ElementKind::Slot { .. } => f(elem),
@@ -1255,7 +1294,7 @@ pub(crate) fn for_each_slot_in_subtree<'a>(
     });
 }
 
-pub(crate) fn for_each_slot<'a>(template: &'a Template, mut f: impl FnMut(&Element)) {
+pub(crate) fn for_each_slot<'a>(template: &'a Template, mut f: impl FnMut(&'a Element)) {
     for_each_template_root(&template, |node, scopes| {
         for_each_slot_in_subtree(node, scopes, &mut f);
     });
@@ -1341,3 +1380,178 @@ pub(crate) fn for_each_static_class_name_in_element<'a>(
         }
     }
 }
+
+/// Iterate over the attributes of an element.
+///
+/// The first argument is the location of the attribute name (including the prefix),
+/// and the second argument is the location of the attribute value (if any).
+pub(crate) fn for_each_attribute_in_element(
+    elem: &Element,
+    mut f: impl FnMut(Range<Position>, Option<Range<Position>>),
+) {
+    fn handle_attr(attr: &Attribute, f: &mut impl FnMut(Range<Position>, Option<Range<Position>>)) {
+        let start = match attr.prefix_location.as_ref() {
+            Some(x) => x.start,
+            None => attr.name.location.start,
+        };
+        f(
+            start..attr.name.location.end,
+            attr.value.as_ref().map(|x| x.location()),
+        );
+    }
+    fn handle_static_attr(
+        attr: &StaticAttribute,
+        f: &mut impl FnMut(Range<Position>, Option<Range<Position>>),
+    ) {
+        let start = match attr.prefix_location.as_ref() {
+            Some(x) => x.start,
+            None => attr.name.location.start,
+        };
+        f(
+            start..attr.name.location.end,
+            Some(attr.value.location.clone()),
+        );
+    }
+    fn handle_common(
+        common: &CommonElementAttributes,
+        f: &mut impl FnMut(Range<Position>, Option<Range<Position>>),
+    ) {
+        if let Some((loc, value)) = common.id.as_ref() {
+            f(loc.clone(), Some(value.location()));
+        }
+        if let Some((loc, value)) = common.slot.as_ref() {
+            f(loc.clone(), Some(value.location()));
+        }
+        for attr in common.slot_value_refs.iter() {
+            handle_static_attr(attr, f);
+        }
+        for ev in common.event_bindings.iter() {
+            f(
+                ev.prefix_location.start..ev.name.location.end,
+                ev.value.as_ref().map(|x| x.location()),
+            );
+        }
+        for attr in common.data.iter().chain(common.marks.iter()) {
+            handle_attr(attr, f);
+        }
+    }
+    match &elem.kind {
+        ElementKind::Normal {
+            attributes,
+            class,
+            style,
+            change_attributes,
+            worklet_attributes,
+            generics,
+            extra_attr,
+            let_vars,
+            common,
+            ..
+        } => {
+            for attr in attributes.iter() {
+                let start = match &attr.prefix {
+                    NormalAttributePrefix::Model(x) => x.start,
+                    NormalAttributePrefix::None => attr.name.location.start,
+                };
+                f(
+                    start..attr.name.location.end,
+                    attr.value.as_ref().map(|x| x.location()),
+                );
+            }
+            match class {
+                ClassAttribute::None => {}
+                ClassAttribute::String(loc, value) => f(loc.clone(), Some(value.location())),
+                ClassAttribute::Multiple(v) => {
+                    for (loc, name, value) in v {
+                        f(
+                            loc.start..name.location.end,
+                            value.as_ref().map(|x| x.location()),
+                        );
+                    }
+                }
+                _ => {}
+            }
+            match style {
+                StyleAttribute::None => {}
+                StyleAttribute::String(loc, value) => f(loc.clone(), Some(value.location())),
+                StyleAttribute::Multiple(v) => {
+                    for (loc, name, value) in v {
+                        f(loc.start..name.location.end, Some(value.location()));
+                    }
+                }
+                _ => {}
+            }
+            for attr in change_attributes.iter().chain(let_vars.iter()) {
+                handle_attr(attr, &mut f);
+            }
+            for attr in worklet_attributes
+                .iter()
+                .chain(generics.iter())
+                .chain(extra_attr.iter())
+            {
+                handle_static_attr(attr, &mut f);
+            }
+            handle_common(common, &mut f);
+        }
+        ElementKind::Pure {
+            let_vars,
+            slot,
+            slot_value_refs,
+            ..
+        } => {
+            for attr in let_vars.iter() {
+                handle_attr(attr, &mut f);
+            }
+            if let Some((loc, value)) = slot {
+                f(loc.clone(), Some(value.location()));
+            }
+            for attr in slot_value_refs.iter() {
+                handle_static_attr(attr, &mut f);
+            }
+        }
+        ElementKind::For {
+            list,
+            item_name,
+            index_name,
+            key,
+            ..
+        } => {
+            f(list.0.clone(), Some(list.1.location()));
+            for (loc, value) in [item_name, index_name, key] {
+                f(loc.clone(), Some(value.location.clone()));
+            }
+        }
+        ElementKind::If {
+            branches,
+            else_branch,
+            ..
+        } => {
+            for (loc, value, _) in branches.iter() {
+                f(loc.clone(), Some(value.location()));
+            }
+            if let Some((loc, _)) = else_branch {
+                f(loc.clone(), None);
+            }
+        }
+        ElementKind::TemplateRef { target, data, .. } => {
+            f(target.0.clone(), Some(target.1.location()));
+            f(data.0.clone(), Some(data.1.location()));
+        }
+        ElementKind::Include { path, .. } => {
+            f(path.0.clone(), Some(path.1.location.clone()));
+        }
+        ElementKind::Slot {
+            name,
+            values,
+            common,
+            ..
+        } => {
+            f(name.0.clone(), Some(name.1.location()));
+            for attr in values.iter() {
+                handle_attr(attr, &mut f);
+            }
+            handle_common(common, &mut f);
+        }
+        _ => {}
+    }
+}
@@ -1,11 +1,15 @@
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
 use glass_easel_template_compiler::parse::Position;
 use lsp_types::FoldingRangeKind;
-use swc_common::{comments::SingleThreadedComments, BytePos};
+use swc_common::{comments::SingleThreadedComments, BytePos, Spanned};
+use swc_ecma_ast::{
+    AssignOp, AssignTarget, Decl, Expr, MemberExpr, MemberProp, ModuleItem, Program, Prop,
+    PropName, PropOrSpread, SimpleAssignTarget, Stmt,
+};
 use swc_ecma_lexer::{
     token::{TokenAndSpan, TokenKind, WordKind},
-    Lexer, StringInput,
+    Lexer, Parser, StringInput,
 };
 
 use crate::{context::project::FileContentMetadata, semantic::TokenType};
@@ -225,3 +229,166 @@ impl ScriptMeta {
         }
     }
 }
+
+/// A function exported by a WXS module.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportedFunction {
+    pub(crate) name: String,
+    pub(crate) params: Vec<String>,
+}
+
+/// Collect the functions exported through `module.exports` , with their parameter lists.
+///
+/// Both `module.exports = { ... }` and `module.exports.name = ...` are recognized.
+/// The exported value can be a function expression or a top-level function binding.
+pub(crate) fn collect_exported_functions(src: &str) -> Vec<ExportedFunction> {
+    let lexer = Lexer::new(
+        swc_ecma_lexer::Syntax::Es(swc_ecma_lexer::EsSyntax::default()),
+        swc_ecma_ast::EsVersion::EsNext,
+        StringInput::new(src, BytePos(0), BytePos(src.len() as u32)),
+        None,
+    );
+    let Ok(program) = Parser::new_from(lexer).parse_program() else {
+        return vec![];
+    };
+    let stmts: Vec<&Stmt> = match &program {
+        Program::Module(module) => module
+            .body
+            .iter()
+            .filter_map(|x| match x {
+                ModuleItem::Stmt(x) => Some(x),
+                _ => None,
+            })
+            .collect(),
+        Program::Script(script) => script.body.iter().collect(),
+    };
+    let source_text = |span: swc_common::Span| -> String {
+        src[span.lo.0 as usize..span.hi.0 as usize].to_string()
+    };
+    let function_params = |expr: &Expr| -> Option<Vec<String>> {
+        match expr {
+            Expr::Fn(x) => Some(
+                x.function
+                    .params
+                    .iter()
+                    .map(|x| source_text(x.pat.span()))
+                    .collect(),
+            ),
+            Expr::Arrow(x) => Some(x.params.iter().map(|x| source_text(x.span())).collect()),
+            _ => None,
+        }
+    };
+
+    // collect the top-level functions
+    let mut locals: HashMap<String, Vec<String>> = HashMap::new();
+    for stmt in stmts.iter() {
+        match stmt {
+            Stmt::Decl(Decl::Fn(x)) => {
+                let params = x
+                    .function
+                    .params
+                    .iter()
+                    .map(|x| source_text(x.pat.span()))
+                    .collect();
+                locals.insert(x.ident.sym.to_string(), params);
+            }
+            Stmt::Decl(Decl::Var(x)) => {
+                for decl in x.decls.iter() {
+                    let (Some(binding), Some(init)) = (decl.name.as_ident(), decl.init.as_ref())
+                    else {
+                        continue;
+                    };
+                    if let Some(params) = function_params(init.unwrap_parens()) {
+                        locals.insert(binding.id.sym.to_string(), params);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let resolve = |expr: &Expr| -> Option<Vec<String>> {
+        let expr = expr.unwrap_parens();
+        match expr {
+            Expr::Ident(x) => locals.get(x.sym.as_str()).cloned(),
+            _ => function_params(expr),
+        }
+    };
+    let is_module_exports = |MemberExpr { obj, prop, .. }: &MemberExpr| {
+        let is_module = matches!(&**obj, Expr::Ident(x) if x.sym == "module");
+        let is_exports = matches!(prop, MemberProp::Ident(x) if x.sym == "exports");
+        is_module && is_exports
+    };
+
+    // collect the exports
+    let mut ret = vec![];
+    for stmt in stmts.iter() {
+        let Stmt::Expr(x) = stmt else {
+            continue;
+        };
+        let Expr::Assign(assign) = &*x.expr else {
+            continue;
+        };
+        if assign.op != AssignOp::Assign {
+            continue;
+        }
+        let AssignTarget::Simple(SimpleAssignTarget::Member(target)) = &assign.left else {
+            continue;
+        };
+        if is_module_exports(target) {
+            let Expr::Object(obj) = assign.right.unwrap_parens() else {
+                continue;
+            };
+            for prop in obj.props.iter() {
+                let PropOrSpread::Prop(prop) = prop else {
+                    continue;
+                };
+                let (name, params) = match &**prop {
+                    Prop::Shorthand(x) => (x.sym.to_string(), locals.get(x.sym.as_str()).cloned()),
+                    Prop::KeyValue(x) => {
+                        let name = match &x.key {
+                            PropName::Ident(x) => x.sym.to_string(),
+                            PropName::Str(x) => x.value.to_string(),
+                            _ => continue,
+                        };
+                        (name, resolve(&x.value))
+                    }
+                    Prop::Method(x) => {
+                        let name = match &x.key {
+                            PropName::Ident(x) => x.sym.to_string(),
+                            PropName::Str(x) => x.value.to_string(),
+                            _ => continue,
+                        };
+                        let params = x
+                            .function
+                            .params
+                            .iter()
+                            .map(|x| source_text(x.pat.span()))
+                            .collect();
+                        (name, Some(params))
+                    }
+                    _ => continue,
+                };
+                if let Some(params) = params {
+                    ret.push(ExportedFunction { name, params });
+                }
+            }
+        } else {
+            let Expr::Member(obj) = &*target.obj else {
+                continue;
+            };
+            let MemberProp::Ident(name) = &target.prop else {
+                continue;
+            };
+            if !is_module_exports(obj) {
+                continue;
+            }
+            if let Some(params) = resolve(&assign.right) {
+                ret.push(ExportedFunction {
+                    name: name.sym.to_string(),
+                    params,
+                });
+            }
+        }
+    }
+    ret
+}
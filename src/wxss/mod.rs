@@ -16,6 +16,7 @@ pub(crate) mod keyframe;
 pub(crate) mod media;
 pub(crate) mod property;
 pub(crate) mod rule;
+pub(crate) mod stringify;
 pub(crate) mod token;
 
 pub(crate) trait CSSParse: Sized {
@@ -321,7 +322,7 @@ impl<T: CSSParse> MaybeUnknown<T> {
         }
     }
 
-    fn location(&self) -> Option<Location> {
+    pub(crate) fn location(&self) -> Option<Location> {
         match self {
             Self::Unknown(x) => x.last().map(|x| x.location()),
             Self::Normal(x, trailing) => {
@@ -486,8 +487,9 @@ mod state {
                 location,
             }),
             CSSToken::Function(name) => {
-                let left = location.end..location.end;
+                // the same as `parse_function`, the left part is `name(` and the right part is `)`
                 let right = location.end..location.end;
+                let left = location;
                 let name = CompactString::new(name);
                 TokenTree::Function(Function {
                     name,
@@ -564,12 +566,12 @@ mod state {
 
         // try parse color, and then revert to corrent position
         pub(super) fn check_color(&mut self) {
+            self.skip_comments();
             let Self {
                 parser,
                 special_locations,
                 ..
             } = self;
-            parser.skip_whitespace();
             let state = parser.state();
             let start_pos = parser_position(&parser);
             if let Ok(color) = cssparser_color::Color::parse(parser) {
@@ -649,7 +651,7 @@ mod state {
                                 }
                             };
                             if token.children().is_some() {
-                                let children = parser
+                                let (children, right_pos) = parser
                                     .parse_nested_block::<_, _, ()>(|parser| {
                                         let mut ps = ParseState {
                                             parser,
@@ -657,11 +659,13 @@ mod state {
                                             comments,
                                             special_locations,
                                         };
-                                        Ok(ps.skip_to_end())
+                                        let children = ps.skip_to_end();
+                                        ps.skip_comments();
+                                        Ok((children, ps.position()))
                                     })
                                     .unwrap();
-                                let right_pos = parser_position(&parser);
-                                let location = right_pos..end_pos;
+                                // the closing token, which is empty if the group is not closed
+                                let location = right_pos..parser_position(parser);
                                 match &mut token {
                                     TokenTree::Function(x) => {
                                         x.children = children;
@@ -829,6 +833,7 @@ mod state {
                 Some(TokenTree::Function(..)) => {}
                 _ => return None,
             }
+            self.skip_comments();
             let state = self.parser.state();
             let start_pos = self.position();
             let Ok(CSSToken::Function(name_str)) = self.parser.next().cloned() else {
@@ -858,6 +863,7 @@ mod state {
                 Some(TokenTree::Paren(..)) => {}
                 _ => return None,
             }
+            self.skip_comments();
             let state = self.parser.state();
             let start_pos = self.position();
             let _ = self.parser.next();
@@ -883,6 +889,7 @@ mod state {
                 Some(TokenTree::Bracket(..)) => {}
                 _ => return None,
             }
+            self.skip_comments();
             let state = self.parser.state();
             let start_pos = self.position();
             let _ = self.parser.next();
@@ -908,6 +915,7 @@ mod state {
                 Some(TokenTree::Brace(..)) => {}
                 _ => return None,
             }
+            self.skip_comments();
             let state = self.parser.state();
             let start_pos = self.position();
             let _ = self.parser.next();
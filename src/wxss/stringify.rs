@@ -0,0 +1,621 @@
+//! The WXSS pretty-printer.
+
+use crate::context::project::FileContentMetadata;
+
+use super::{
+    keyframe::Keyframe,
+    property::Property,
+    rule::Selector,
+    token::{BraceOrSemicolon, Comment, TokenTree},
+    CSSParse, List, Location, Position, Rule, RuleOrProperty, StyleSheet,
+};
+
+/// The options of the WXSS pretty-printer.
+#[derive(Debug, Clone)]
+pub(crate) struct StringifyOptions {
+    pub(crate) tab_size: u32,
+    pub(crate) use_tab_character: bool,
+    /// Write hex colors like `#FFF` in lowercase.
+    pub(crate) lowercase_hex_colors: bool,
+    /// Sort the adjacent declarations (not separated by blank lines or comments) by property names.
+    ///
+    /// The related declarations (like `margin` and `margin-top` ) keep their relative order.
+    pub(crate) sort_properties: bool,
+}
+
+impl Default for StringifyOptions {
+    fn default() -> Self {
+        Self {
+            tab_size: 4,
+            use_tab_character: false,
+            lowercase_hex_colors: false,
+            sort_properties: false,
+        }
+    }
+}
+
+/// Pretty-print a style sheet parsed from `content` .
+///
+/// Comments are kept in place.
+/// The parts which are not fully parsed (like unknown at-rules) are copied from the source verbatim.
+pub(crate) fn stringify_style_sheet(
+    sheet: &StyleSheet,
+    content: &FileContentMetadata,
+    options: &StringifyOptions,
+) -> String {
+    let mut w = Writer::new(sheet, content, options);
+    let entries = w.entries(&WHOLE, sheet.items.iter().map(Entry::Rule).collect());
+    w.write_entries(0, entries);
+    w.out
+}
+
+/// Pretty-print the entries covered by `range` , keeping the indentation of the line they start.
+///
+/// The entries are searched in the innermost block containing `range` .
+/// Returns the location of these entries and the formatted content.
+pub(crate) fn stringify_style_sheet_range(
+    sheet: &StyleSheet,
+    content: &FileContentMetadata,
+    options: &StringifyOptions,
+    range: &Location,
+) -> Option<(Location, String)> {
+    let mut w = Writer::new(sheet, content, options);
+    let mut entries = w.entries(&WHOLE, sheet.items.iter().map(Entry::Rule).collect());
+    let covered = loop {
+        let mut covered = vec![];
+        let mut containing = None;
+        for entry in entries {
+            let loc = entry.location();
+            if contains(range, &loc) {
+                covered.push(entry);
+            } else if contains(&loc, range) {
+                containing = Some(entry);
+            }
+        }
+        if !covered.is_empty() {
+            break covered;
+        }
+        entries = w.child_entries(containing?)?;
+    };
+    let location = covered.first()?.location().start..covered.last()?.location().end;
+    w.indent = content.line_indent(location.start.line);
+    w.write_entries(0, covered);
+    let out = w.out.trim_end_matches('\n');
+    Some((location, out[w.indent.len()..].to_string()))
+}
+
+const WHOLE: Location = Position {
+    line: 0,
+    utf16_col: 0,
+}..Position {
+    line: u32::MAX,
+    utf16_col: u32::MAX,
+};
+
+enum Entry<'a> {
+    Rule(&'a Rule),
+    Property(&'a Property),
+    Keyframe(&'a Keyframe),
+    Comment(&'a Comment),
+}
+
+impl<'a> Entry<'a> {
+    fn location(&self) -> Location {
+        match self {
+            Self::Rule(x) => x.location(),
+            Self::Property(x) => x.location(),
+            Self::Keyframe(x) => x.location(),
+            Self::Comment(x) => x.location.clone(),
+        }
+    }
+}
+
+/// An entry written in its own line(s).
+struct Line<'a> {
+    entry: Entry<'a>,
+    /// The comments following the entry in the same line.
+    trailing_comments: Vec<&'a Comment>,
+    blank_line_before: bool,
+}
+
+struct Writer<'a> {
+    content: &'a FileContentMetadata,
+    /// The indentation written before the indentation of each level.
+    indent: &'a str,
+    comments: Vec<&'a Comment>,
+    options: &'a StringifyOptions,
+    out: String,
+}
+
+fn contains(outer: &Location, inner: &Location) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+fn body_start<T>(body: &Option<BraceOrSemicolon<T>>, end: Position) -> Position {
+    match body {
+        None => end,
+        Some(BraceOrSemicolon::Brace(x)) => x.left.start,
+        Some(BraceOrSemicolon::UnknownBrace(x)) => x.left.start,
+        Some(BraceOrSemicolon::Semicolon(x)) => x.location().start,
+    }
+}
+
+fn rule_or_property_entries(list: &List<RuleOrProperty>) -> Vec<Entry<'_>> {
+    list.iter()
+        .map(|x| match x {
+            RuleOrProperty::Rule(x) => Entry::Rule(x),
+            RuleOrProperty::Property(x) => Entry::Property(x),
+        })
+        .collect()
+}
+
+/// Collapse whitespace outside quoted strings into single spaces.
+fn collapse_whitespace(s: &str) -> String {
+    let mut ret = String::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut pending_space = false;
+    for c in s.trim().chars() {
+        if let Some(q) = quote {
+            ret.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space {
+            ret.push(' ');
+            pending_space = false;
+        }
+        if c == '"' || c == '\'' {
+            quote = Some(c);
+        }
+        ret.push(c);
+    }
+    ret
+}
+
+impl<'a> Writer<'a> {
+    fn new(
+        sheet: &'a StyleSheet,
+        content: &'a FileContentMetadata,
+        options: &'a StringifyOptions,
+    ) -> Self {
+        let mut comments: Vec<&Comment> = sheet.comments.iter().collect();
+        comments.sort_by_key(|x| x.location.start);
+        comments.dedup_by(|a, b| a.location == b.location);
+        Self {
+            content,
+            indent: "",
+            comments,
+            options,
+            out: String::new(),
+        }
+    }
+
+    fn slice(&self, loc: &Location) -> &'a str {
+        let content = self.content;
+        let start = content.content_index_for_line_utf16_col(loc.start.line, loc.start.utf16_col);
+        let end = content.content_index_for_line_utf16_col(loc.end.line, loc.end.utf16_col);
+        content
+            .content
+            .get(start..end.max(start))
+            .unwrap_or_default()
+    }
+
+    fn has_comment_in(&self, loc: &Location) -> bool {
+        self.comments.iter().any(|x| contains(loc, &x.location))
+    }
+
+    fn write_indent(&mut self, level: usize) {
+        self.out.push_str(self.indent);
+        if self.options.use_tab_character {
+            self.out.push_str(&"\t".repeat(level));
+        } else {
+            let n = self.options.tab_size as usize * level;
+            self.out.push_str(&" ".repeat(n));
+        }
+    }
+
+    /// Merge the comments in `range` which are not inside any item into the items.
+    fn entries(&self, range: &Location, items: Vec<Entry<'a>>) -> Vec<Entry<'a>> {
+        let item_locations: Vec<_> = items.iter().map(|x| x.location()).collect();
+        let mut comments = self
+            .comments
+            .iter()
+            .filter(|c| {
+                contains(range, &c.location)
+                    && !item_locations.iter().any(|x| contains(x, &c.location))
+            })
+            .peekable();
+        let mut ret = vec![];
+        for (item, loc) in items.into_iter().zip(item_locations.iter()) {
+            while let Some(c) = comments.next_if(|c| c.location.start < loc.start) {
+                ret.push(Entry::Comment(c));
+            }
+            ret.push(item);
+        }
+        ret.extend(comments.map(|c| Entry::Comment(c)));
+        ret
+    }
+
+    fn write_entries(&mut self, level: usize, entries: Vec<Entry<'a>>) {
+        // attach comments to the entries in the same line and find blank lines
+        let mut lines: Vec<Line> = vec![];
+        let mut prev_end: Option<Position> = None;
+        for entry in entries {
+            let loc = entry.location();
+            if let (Entry::Comment(c), Some(prev)) = (&entry, lines.last_mut()) {
+                if prev_end.is_some_and(|x| x.line == loc.start.line) {
+                    prev.trailing_comments.push(*c);
+                    prev_end = Some(loc.end);
+                    continue;
+                }
+            }
+            let blank_line_before = prev_end.is_some_and(|x| loc.start.line > x.line + 1);
+            prev_end = Some(loc.end);
+            lines.push(Line {
+                entry,
+                trailing_comments: vec![],
+                blank_line_before,
+            });
+        }
+
+        // sort the declarations in each group
+        if self.options.sort_properties {
+            let mut start = 0;
+            while start < lines.len() {
+                let mut end = start + 1;
+                if matches!(lines[start].entry, Entry::Property(_)) {
+                    while end < lines.len()
+                        && !lines[end].blank_line_before
+                        && matches!(lines[end].entry, Entry::Property(_))
+                    {
+                        end += 1;
+                    }
+                    let blank_line_before = lines[start].blank_line_before;
+                    let group: Vec<_> = lines.drain(start..end).collect();
+                    lines.splice(start..start, sort_declarations(group));
+                    for (i, line) in lines[start..end].iter_mut().enumerate() {
+                        line.blank_line_before = i == 0 && blank_line_before;
+                    }
+                }
+                start = end;
+            }
+        }
+
+        for line in lines {
+            if line.blank_line_before {
+                self.out.push('\n');
+            }
+            self.write_indent(level);
+            match line.entry {
+                Entry::Rule(x) => self.write_rule(level, x),
+                Entry::Property(x) => self.write_property(x),
+                Entry::Keyframe(x) => self.write_keyframe(level, x),
+                Entry::Comment(x) => self.out.push_str(self.slice(&x.location)),
+            }
+            for c in line.trailing_comments {
+                self.out.push(' ');
+                self.out.push_str(self.slice(&c.location));
+            }
+            self.out.push('\n');
+        }
+    }
+
+    /// Write the part before the body, collapsing whitespace if there is no comment inside.
+    fn write_prelude(&mut self, loc: &Location) {
+        let s = self.slice(loc);
+        if self.has_comment_in(loc) {
+            self.out.push_str(s.trim());
+        } else {
+            self.out.push_str(&collapse_whitespace(s));
+        }
+    }
+
+    /// Get the entries in the block, or `None` if the block is copied verbatim.
+    fn block_entries<T>(
+        &self,
+        body: &'a Option<BraceOrSemicolon<T>>,
+        items: impl FnOnce(&'a T) -> Vec<Entry<'a>>,
+    ) -> Option<Vec<Entry<'a>>> {
+        match body {
+            // a block containing nothing but comments is also unknown
+            Some(BraceOrSemicolon::UnknownBrace(x)) if x.trailing.is_empty() => {
+                Some(self.entries(&(x.left.end..x.right.start), vec![]))
+            }
+            Some(BraceOrSemicolon::Brace(x)) if x.trailing.is_empty() => {
+                let inner = x.left.end..x.right.start;
+                Some(self.entries(&inner, items(&x.children)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the entries in the block of the entry, if it has one.
+    fn child_entries(&self, entry: Entry<'a>) -> Option<Vec<Entry<'a>>> {
+        match entry {
+            Entry::Rule(Rule::Style(x)) => self.block_entries(&x.brace, rule_or_property_entries),
+            Entry::Rule(Rule::Media(x)) => {
+                self.block_entries(&x.body, |list| list.iter().map(Entry::Rule).collect())
+            }
+            Entry::Rule(Rule::FontFace(x)) => self.block_entries(&x.body, rule_or_property_entries),
+            Entry::Rule(Rule::Keyframes(x)) => {
+                self.block_entries(&x.body, |list| list.iter().map(Entry::Keyframe).collect())
+            }
+            Entry::Keyframe(Keyframe::Named { body, .. })
+            | Entry::Keyframe(Keyframe::Percentage { body, .. }) => {
+                self.block_entries(body, rule_or_property_entries)
+            }
+            _ => None,
+        }
+    }
+
+    fn write_body<T>(
+        &mut self,
+        level: usize,
+        body: &'a Option<BraceOrSemicolon<T>>,
+        items: impl FnOnce(&'a T) -> Vec<Entry<'a>>,
+    ) {
+        if let Some(entries) = self.block_entries(body, items) {
+            self.write_block(level, entries);
+            return;
+        }
+        match body {
+            None => {}
+            Some(BraceOrSemicolon::Semicolon(_)) => self.out.push(';'),
+            Some(BraceOrSemicolon::UnknownBrace(x)) => {
+                self.out.push(' ');
+                self.out.push_str(self.slice(&x.location()));
+            }
+            Some(BraceOrSemicolon::Brace(x)) => {
+                self.out.push(' ');
+                self.out.push_str(self.slice(&x.location()));
+            }
+        }
+    }
+
+    fn write_block(&mut self, level: usize, entries: Vec<Entry<'a>>) {
+        if entries.is_empty() {
+            self.out.push_str(" {}");
+            return;
+        }
+        self.out.push_str(" {\n");
+        self.write_entries(level + 1, entries);
+        self.write_indent(level);
+        self.out.push('}');
+    }
+
+    fn write_rule(&mut self, level: usize, rule: &'a Rule) {
+        let loc = rule.location();
+        match rule {
+            Rule::Unknown(_) | Rule::UnknownAtRule(..) => {
+                self.out.push_str(self.slice(&loc));
+            }
+            Rule::Style(x) => {
+                let prelude = loc.start..body_start(&x.brace, loc.end);
+                let selector_end = x.selector.location().end;
+                let garbage = self.slice(&(selector_end..prelude.end));
+                if self.has_comment_in(&prelude) || !garbage.trim().is_empty() {
+                    self.write_prelude(&prelude);
+                } else {
+                    for (i, (list, comma)) in x.selector.iter_items().enumerate() {
+                        if i > 0 {
+                            self.out.push('\n');
+                            self.write_indent(level);
+                        }
+                        let s = self.format_selector(list);
+                        self.out.push_str(&s);
+                        if comma.is_some() {
+                            self.out.push(',');
+                        }
+                    }
+                }
+                self.write_body(level, &x.brace, rule_or_property_entries);
+            }
+            Rule::Import(x) => {
+                let end = x.semicolon.as_ref().map(|x| x.location().start);
+                self.write_prelude(&(loc.start..end.unwrap_or(loc.end)));
+                self.out.push(';');
+            }
+            Rule::Media(x) => {
+                self.write_prelude(&(loc.start..body_start(&x.body, loc.end)));
+                self.write_body(level, &x.body, |list| {
+                    list.iter().map(Entry::Rule).collect()
+                });
+            }
+            Rule::FontFace(x) => {
+                self.write_prelude(&(loc.start..body_start(&x.body, loc.end)));
+                self.write_body(level, &x.body, rule_or_property_entries);
+            }
+            Rule::Keyframes(x) => {
+                self.write_prelude(&(loc.start..body_start(&x.body, loc.end)));
+                self.write_body(level, &x.body, |list| {
+                    list.iter().map(Entry::Keyframe).collect()
+                });
+            }
+        }
+    }
+
+    fn write_keyframe(&mut self, level: usize, keyframe: &'a Keyframe) {
+        let loc = keyframe.location();
+        let body = match keyframe {
+            Keyframe::Named { body, .. } => body,
+            Keyframe::Percentage { body, .. } => body,
+            Keyframe::Unknown(_) => {
+                self.out.push_str(self.slice(&loc));
+                return;
+            }
+        };
+        self.write_prelude(&(loc.start..body_start(body, loc.end)));
+        self.write_body(level, body, rule_or_property_entries);
+    }
+
+    fn write_property(&mut self, prop: &Property) {
+        let loc = prop.location();
+        if self.has_comment_in(&loc) {
+            self.out.push_str(self.slice(&loc));
+            if prop.semicolon.is_none() {
+                self.out.push(';');
+            }
+            return;
+        }
+        self.out.push_str(self.slice(&prop.name.location));
+        self.out.push(':');
+        if !prop.value.is_empty() {
+            self.out.push(' ');
+            let s = self.format_tokens(&prop.value);
+            self.out.push_str(&s);
+        }
+        self.out.push(';');
+    }
+
+    /// Format a complex selector, with single spaces around the combinators.
+    fn format_selector(&self, list: &List<Selector>) -> String {
+        let mut ret = String::new();
+        let mut prev_end: Option<Position> = None;
+        for sel in list.iter() {
+            let loc = sel.location();
+            match sel {
+                Selector::NextSibling(_)
+                | Selector::Child(_)
+                | Selector::Column(..)
+                | Selector::SubsequentSibling(_) => {
+                    if !ret.is_empty() {
+                        ret.push(' ');
+                    }
+                    ret.push_str(self.slice(&loc));
+                    ret.push(' ');
+                    prev_end = None;
+                }
+                _ => {
+                    // whitespace between compound selectors is the descendant combinator
+                    if prev_end.is_some_and(|x| x < loc.start) {
+                        ret.push(' ');
+                    }
+                    ret.push_str(self.slice(&loc));
+                    prev_end = Some(loc.end);
+                }
+            }
+        }
+        ret.truncate(ret.trim_end().len());
+        ret
+    }
+
+    /// Format a value, keeping at most one space between tokens and a space after each comma.
+    fn format_tokens(&self, tokens: &[TokenTree]) -> String {
+        let mut ret = String::new();
+        let mut prev: Option<&TokenTree> = None;
+        for tt in tokens {
+            if let Some(prev) = prev {
+                let space = match (prev, tt) {
+                    (_, TokenTree::Comma(_)) => false,
+                    (TokenTree::Comma(_), _) => true,
+                    _ => prev.location().end < tt.location().start,
+                };
+                if space {
+                    ret.push(' ');
+                }
+            }
+            match tt {
+                TokenTree::Function(x) => {
+                    ret.push_str(self.slice(&x.left));
+                    ret.push_str(&self.format_tokens(&x.children));
+                    ret.push_str(self.slice(&x.right));
+                }
+                TokenTree::Paren(x) => {
+                    ret.push_str(self.slice(&x.left));
+                    ret.push_str(&self.format_tokens(&x.children));
+                    ret.push_str(self.slice(&x.right));
+                }
+                TokenTree::Bracket(x) => {
+                    ret.push_str(self.slice(&x.left));
+                    ret.push_str(&self.format_tokens(&x.children));
+                    ret.push_str(self.slice(&x.right));
+                }
+                TokenTree::Hash(_) | TokenTree::IDHash(_) => {
+                    let s = self.slice(&tt.location());
+                    let digits = s.strip_prefix('#').unwrap_or_default();
+                    let is_color = [3, 4, 6, 8].contains(&digits.len())
+                        && digits.chars().all(|c| c.is_ascii_hexdigit());
+                    if self.options.lowercase_hex_colors && is_color {
+                        ret.push_str(&s.to_ascii_lowercase());
+                    } else {
+                        ret.push_str(s);
+                    }
+                }
+                _ => ret.push_str(self.slice(&tt.location())),
+            }
+            prev = Some(tt);
+        }
+        ret
+    }
+}
+
+/// Sort the declarations by property names.
+///
+/// The related declarations (like `margin` and `margin-top` ) keep their relative order,
+/// since the later one overrides (a part of) the former one.
+fn sort_declarations(group: Vec<Line>) -> Vec<Line> {
+    let names: Vec<_> = group
+        .iter()
+        .map(|x| match x.entry {
+            Entry::Property(p) => p.name.content.to_ascii_lowercase(),
+            _ => unreachable!(),
+        })
+        .collect();
+    let mut remaining: Vec<usize> = (0..group.len()).collect();
+    let mut order = Vec::with_capacity(group.len());
+    while !remaining.is_empty() {
+        // pick the smallest name which is not related to any declaration before it
+        let k = (0..remaining.len())
+            .filter(|&k| {
+                let i = remaining[k];
+                !remaining[..k]
+                    .iter()
+                    .any(|&j| related_properties(&names[j], &names[i]))
+            })
+            .min_by(|&a, &b| names[remaining[a]].cmp(&names[remaining[b]]))
+            .unwrap_or(0);
+        order.push(remaining.remove(k));
+    }
+    let mut group: Vec<_> = group.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| group[i].take()).collect()
+}
+
+/// Check whether two properties may set the same longhand property.
+///
+/// This is a conservative guess: the properties with the same leading word
+/// (ignoring vendor prefixes) are treated as related.
+fn related_properties(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    if a.starts_with("--") || b.starts_with("--") {
+        return false;
+    }
+    fn family(name: &str) -> &str {
+        let name = match name.strip_prefix('-') {
+            Some(x) => x.split_once('-').map(|(_, x)| x).unwrap_or(x),
+            None => name,
+        };
+        match name {
+            "top" | "right" | "bottom" | "left" => "inset",
+            "row-gap" | "column-gap" => "gap",
+            "line-height" => "font",
+            _ => match name.split('-').next().unwrap_or(name) {
+                "align" | "justify" => "place",
+                x => x,
+            },
+        }
+    }
+    family(a) == family(b)
+}
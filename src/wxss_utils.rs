@@ -3,6 +3,7 @@ use crate::{
     wxss::{
         keyframe::Keyframe,
         media::*,
+        property::Property,
         rule::{IdentOrFunction, Selector},
         token::*,
         CSSParse, List, MaybeUnknown, Position, Rule, RuleOrProperty, StyleSheet,
@@ -467,6 +468,39 @@ pub(crate) fn for_each_selector_in_style_sheet(sheet: &StyleSheet, mut f: impl F
     });
 }
 
+pub(crate) fn for_each_property_in_style_sheet(sheet: &StyleSheet, mut f: impl FnMut(&Property)) {
+    fn rec_in_option_brace(
+        x: &Option<BraceOrSemicolon<List<RuleOrProperty>>>,
+        f: &mut impl FnMut(&Property),
+    ) {
+        if let Some(BraceOrSemicolon::Brace(t)) = x {
+            for x in t.children.iter() {
+                if let RuleOrProperty::Property(prop) = x {
+                    f(prop);
+                }
+            }
+        }
+    }
+    for_each_rule_in_style_sheet(sheet, |rule| match rule {
+        Rule::Style(x) => rec_in_option_brace(&x.brace, &mut f),
+        Rule::FontFace(x) => rec_in_option_brace(&x.body, &mut f),
+        Rule::Keyframes(x) => {
+            if let Some(BraceOrSemicolon::Brace(t)) = &x.body {
+                for keyframe in t.children.iter() {
+                    match keyframe {
+                        Keyframe::Named { progress: _, body }
+                        | Keyframe::Percentage { progress: _, body } => {
+                            rec_in_option_brace(body, &mut f);
+                        }
+                        Keyframe::Unknown(_) => {}
+                    }
+                }
+            }
+        }
+        _ => {}
+    });
+}
+
 pub(crate) fn for_each_import_in_style_sheet(sheet: &StyleSheet, mut f: impl FnMut(&str)) {
     for_each_rule_in_style_sheet(sheet, |rule| match rule {
         Rule::Import(x) => match &x.url {
@@ -0,0 +1,323 @@
+//! Run the command-line interface against the fixtures of the VS Code extension tests.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+use serde_json::{json, Value};
+
+fn root_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn fixture_dir() -> PathBuf {
+    root_dir().join("vscode-extension").join("test-fixture")
+}
+
+fn sample_backend_config() -> PathBuf {
+    root_dir().join("backend-configuration").join("sample.toml")
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_glass-easel-analyzer"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn check(extra_args: &[&str]) -> Output {
+    let dir = fixture_dir();
+    let backend_config = sample_backend_config();
+    let mut args = vec![
+        "check",
+        dir.to_str().unwrap(),
+        "--backend-config",
+        backend_config.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra_args);
+    run(&args)
+}
+
+/// A fresh directory for the files written by a test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "glass-easel-analyzer-test-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn check_human() {
+    let out = check(&[]);
+    assert_eq!(out.status.code(), Some(1));
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.lines().any(|x| x
+        == "component/backend.wxml:4:2: warning: missing property `src` which is required by component <image>"));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("error(s)"));
+}
+
+#[test]
+fn check_fail_on() {
+    assert_eq!(check(&["--fail-on", "never"]).status.code(), Some(0));
+    assert_eq!(check(&["--fail-on", "hint"]).status.code(), Some(1));
+}
+
+#[test]
+fn check_json() {
+    let out = check(&["--format", "json", "--byte-offsets"]);
+    assert_eq!(out.status.code(), Some(1));
+    let lines: Vec<Value> = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|x| serde_json::from_str(x).unwrap())
+        .collect();
+    assert!(!lines.is_empty());
+    for x in lines.iter() {
+        assert!(x["file"].is_string());
+        assert!(x["message"].is_string());
+        assert!(
+            ["error", "warning", "information", "hint"].contains(&x["severity"].as_str().unwrap())
+        );
+        assert!(x["range"]["start"]["line"].is_u64());
+        assert!(x["range"]["end"]["character"].is_u64());
+    }
+    let x = lines
+        .iter()
+        .find(|x| {
+            x["file"] == "component/backend.wxml"
+                && x["message"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("missing property `src`")
+        })
+        .unwrap();
+    assert_eq!(
+        x["range"],
+        json!({ "start": { "line": 3, "character": 1 }, "end": { "line": 3, "character": 6 } })
+    );
+    assert!(lines.iter().any(|x| x["severity"] == "error"));
+}
+
+#[test]
+fn check_sarif() {
+    let out = check(&["--format", "sarif"]);
+    assert_eq!(out.status.code(), Some(1));
+    let log: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let runs = log["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0]["tool"]["driver"]["name"], "glass-easel-analyzer");
+    let results = runs[0]["results"].as_array().unwrap();
+    assert!(!results.is_empty());
+    for x in results {
+        assert!(["error", "warning", "note"].contains(&x["level"].as_str().unwrap()));
+        assert!(x["message"]["text"].is_string());
+        let loc = &x["locations"][0]["physicalLocation"];
+        assert!(loc["artifactLocation"]["uri"].is_string());
+        assert!(loc["region"]["startLine"].as_u64().unwrap() >= 1);
+        assert!(loc["region"]["startColumn"].as_u64().unwrap() >= 1);
+    }
+    let x = results
+        .iter()
+        .find(|x| {
+            x["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+                == "component/backend.wxml"
+                && x["message"]["text"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("missing property `src`")
+        })
+        .unwrap();
+    assert_eq!(x["level"], "warning");
+    assert_eq!(
+        x["locations"][0]["physicalLocation"]["region"],
+        json!({ "startLine": 4, "startColumn": 2, "endLine": 4, "endColumn": 7 })
+    );
+}
+
+#[test]
+fn format_check() {
+    let dir = temp_dir("format-check");
+    let p = dir.join("index.wxml");
+    std::fs::write(&p, "<view><text>a</text></view>\n").unwrap();
+    let out = run(&["format", "--check", dir.to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stdout)
+        .unwrap()
+        .contains(p.to_str().unwrap()));
+    let out = run(&["format", "--write", dir.to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(0));
+    let out = run(&["format", "--check", dir.to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(0));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn backend_config_validate() {
+    let sample = sample_backend_config();
+    let out = run(&["backend-config", "validate", sample.to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(0));
+
+    let invalid = fixture_dir().join("backend-config").join("invalid.toml");
+    let out = run(&[
+        "backend-config",
+        "validate",
+        sample.to_str().unwrap(),
+        invalid.to_str().unwrap(),
+    ]);
+    assert_eq!(out.status.code(), Some(1));
+    let expected = format!("{}:5:13: error: invalid basic string", invalid.display());
+    let output = format!(
+        "{}{}",
+        String::from_utf8(out.stdout).unwrap(),
+        String::from_utf8(out.stderr).unwrap()
+    );
+    assert!(output.lines().any(|x| x == expected), "{}", output);
+}
+
+#[test]
+fn backend_config_diff() {
+    let dir = temp_dir("backend-config-diff");
+    let old = sample_backend_config();
+    let new = dir.join("new.toml");
+    let content = std::fs::read_to_string(&old)
+        .unwrap()
+        .replace("tag-name = \"image\"", "tag-name = \"picture\"");
+    std::fs::write(&new, content).unwrap();
+    let out = run(&[
+        "backend-config",
+        "diff",
+        old.to_str().unwrap(),
+        new.to_str().unwrap(),
+    ]);
+    assert_eq!(out.status.code(), Some(0));
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(
+        stdout.lines().any(|x| x == "- component <image>"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.lines().any(|x| x == "+ component <picture>"),
+        "{}",
+        stdout
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+fn send_message(stream: &mut TcpStream, msg: Value) {
+    let content = serde_json::to_string(&msg).unwrap();
+    write!(
+        stream,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )
+    .unwrap();
+    stream.flush().unwrap();
+}
+
+fn recv_message(reader: &mut BufReader<TcpStream>) -> Value {
+    let mut len = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case("content-length") {
+                len = value.trim().parse().unwrap();
+            }
+        }
+    }
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf).unwrap();
+    serde_json::from_slice(&buf).unwrap()
+}
+
+fn recv_response(reader: &mut BufReader<TcpStream>, id: u64) -> Value {
+    loop {
+        let msg = recv_message(reader);
+        if msg["id"] == id && msg.get("method").is_none() {
+            return msg;
+        }
+    }
+}
+
+/// Initialize a language server session and shut it down.
+fn lsp_session(mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    send_message(
+        &mut stream,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "processId": null, "rootUri": null, "capabilities": {} },
+        }),
+    );
+    let res = recv_response(&mut reader, 1);
+    assert!(res["result"]["capabilities"]["hoverProvider"]
+        .as_bool()
+        .unwrap());
+    send_message(
+        &mut stream,
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+    );
+    send_message(
+        &mut stream,
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown", "params": null }),
+    );
+    recv_response(&mut reader, 2);
+    send_message(
+        &mut stream,
+        json!({ "jsonrpc": "2.0", "method": "exit", "params": null }),
+    );
+}
+
+#[test]
+fn transport_connect() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_glass-easel-analyzer"))
+        .args(["--connect", &addr])
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    lsp_session(stream);
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn transport_listen() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_glass-easel-analyzer"))
+        .args(["--listen", "127.0.0.1:0"])
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    let addr = line
+        .trim()
+        .strip_prefix("listening on ")
+        .unwrap()
+        .to_string();
+    // the clients are served one after another
+    for _ in 0..2 {
+        lsp_session(TcpStream::connect(&addr).unwrap());
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
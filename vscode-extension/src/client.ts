@@ -4,6 +4,8 @@ import {
   type Executable,
   LanguageClient,
   type LanguageClientOptions,
+  type Location,
+  type Position,
 } from 'vscode-languageclient/node'
 import { middleware, updateInlineWxsScripts } from './middleware'
 import { TsServiceHost } from './typescript'
@@ -12,6 +14,7 @@ import { resolveRelativePath } from './utils'
 export type ClientOptions = {
   serverPath: string
   backendConfigPath: string
+  backendConfigOverlays: string[]
   ignorePaths: string[]
   analyzeOtherStylesheets: boolean
   preferredTypescriptVersion: string
@@ -62,6 +65,11 @@ export class Client {
     return this.client?.sendRequest(`glassEaselAnalyzer/${method}`, params)
   }
 
+  // eslint-disable-next-line @typescript-eslint/no-unnecessary-type-parameters
+  async request<Req, Resp>(method: string, params: Req): Promise<Resp | undefined> {
+    return this.client?.sendRequest(method, params)
+  }
+
   async start() {
     let backendConfig = ''
     const homeUri = this.getHomeUri()
@@ -75,6 +83,9 @@ export class Client {
       )
     }
     const workspaceFolders = vscode.workspace.workspaceFolders?.map((x) => x.uri.toString()) ?? []
+    const backendConfigOverlays = this.options.backendConfigOverlays.map((x) =>
+      resolveRelativePath(homeUri, x).toString(),
+    )
     const ignorePaths = this.options.ignorePaths.map((x) =>
       resolveRelativePath(homeUri, x).toString(),
     )
@@ -107,6 +118,12 @@ export class Client {
     const languageClientOptions: LanguageClientOptions = {
       initializationOptions: {
         backendConfig,
+        // allows the server to watch the file
+        backendConfigPath:
+          this.getBackendConfigPath() && backendConfigUrl.scheme === 'file'
+            ? backendConfigUrl.fsPath
+            : undefined,
+        backendConfigOverlays,
         workspaceFolders,
         ignorePaths,
         enableOtherSs: this.options.analyzeOtherStylesheets,
@@ -147,6 +164,17 @@ export class Client {
     await this.client.start()
   }
 
+  async showReferences(uri: string, position: Position, locations: Location[]) {
+    if (!this.client) return
+    const conv = this.client.protocol2CodeConverter
+    await vscode.commands.executeCommand(
+      'editor.action.showReferences',
+      conv.asUri(uri),
+      conv.asPosition(position),
+      await conv.asLocations(locations),
+    )
+  }
+
   async stop() {
     this.tsServerHost?.destroy()
     this.tsServerHost = null
@@ -1,8 +1,13 @@
 import * as vscode from 'vscode'
+import type { Location, Position } from 'vscode-languageclient/node'
 import { Client } from './client'
 
 let languageServer: Client | null = null
 
+export type ExtensionApi = {
+  request: (method: string, params: unknown) => Promise<unknown>
+}
+
 const startLanguageServer = async () => {
   const serverPath = vscode.workspace
     .getConfiguration('glass-easel-analyzer')
@@ -10,6 +15,9 @@ const startLanguageServer = async () => {
   const backendConfigPath = vscode.workspace
     .getConfiguration('glass-easel-analyzer')
     .get('backendConfigurationPath') as string
+  const backendConfigOverlays = vscode.workspace
+    .getConfiguration('glass-easel-analyzer')
+    .get('backendConfigurationOverlays') as string[]
   const ignorePaths = vscode.workspace
     .getConfiguration('glass-easel-analyzer')
     .get('ignorePaths') as string[]
@@ -25,6 +33,7 @@ const startLanguageServer = async () => {
   const options = {
     serverPath,
     backendConfigPath,
+    backendConfigOverlays,
     ignorePaths,
     analyzeOtherStylesheets,
     preferredTypescriptVersion,
@@ -47,12 +56,20 @@ export async function activate(context: vscode.ExtensionContext) {
     await vscode.window.showInformationMessage('glass-easel language server restarted')
   })
   context.subscriptions.push(disposable)
+  const disposable3 = vscode.commands.registerCommand(
+    'glass-easel-analyzer.showReferences',
+    async (uri: string, position: Position, locations: Location[]) => {
+      await languageServer?.showReferences(uri, position, locations)
+    },
+  )
+  context.subscriptions.push(disposable3)
 
   // events
   const disposable2 = vscode.workspace.onDidChangeConfiguration((ev) => {
     const changed =
       ev.affectsConfiguration('glass-easel-analyzer.serverPath') ||
       ev.affectsConfiguration('glass-easel-analyzer.backendConfigurationPath') ||
+      ev.affectsConfiguration('glass-easel-analyzer.backendConfigurationOverlays') ||
       ev.affectsConfiguration('glass-easel-analyzer.ignorePaths') ||
       ev.affectsConfiguration('glass-easel-analyzer.analyzeOtherStylesheets') ||
       ev.affectsConfiguration('glass-easel-analyzer.preferredTypescriptVersion') ||
@@ -77,6 +94,12 @@ export async function activate(context: vscode.ExtensionContext) {
 
   // start
   await startLanguageServer()
+
+  // the raw language server requests, used by tests
+  const api: ExtensionApi = {
+    request: async (method, params) => languageServer?.request(method, params),
+  }
+  return api
 }
 
 export async function deactivate() {
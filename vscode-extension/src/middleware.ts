@@ -1,6 +1,6 @@
 import * as vscode from 'vscode'
 import path from 'node:path'
-import { type Middleware } from 'vscode-languageclient'
+import { type Middleware, vsdiag } from 'vscode-languageclient'
 import {
   getCSSLanguageService,
   getLESSLanguageService,
@@ -174,6 +174,57 @@ export const middleware: Middleware = {
     next(uri, diagnostics)
   },
 
+  async provideDiagnostics(document, previousResultId, token, next) {
+    const uri = document instanceof vscode.Uri ? document : document.uri
+
+    // replace with wxss diagnostics
+    if (path.extname(uri.path) === '.wxss') {
+      const ls = selectCssLanguageService()
+      if (ls) {
+        try {
+          const diag = await doCssValidation(uri, ls)
+          return { kind: vsdiag.DocumentDiagnosticReportKind.full, items: diag as any }
+        } catch {
+          vscode.window.showErrorMessage('Failed to get CSS diagnostics')
+          return { kind: vsdiag.DocumentDiagnosticReportKind.full, items: [] }
+        }
+      }
+      return next(document, previousResultId, token)
+    }
+
+    // append wxml-ts diagnostics
+    if (path.extname(uri.fsPath) === '.wxml') {
+      const service = await TsService.find(uri.fsPath)
+      if (service) {
+        // the full report is always required to merge with
+        const report = await next(document, undefined, token)
+        if (report?.kind === vsdiag.DocumentDiagnosticReportKind.full) {
+          const diags = await service.getDiagnostics(uri.fsPath)
+          report.items.push(...diags)
+        }
+        return report
+      }
+    }
+    return next(document, previousResultId, token)
+  },
+
+  async provideWorkspaceDiagnostics(resultIds, token, resultReporter, next) {
+    // wxss diagnostics are replaced for opened documents only,
+    // but the result ids are kept so that unchanged files are not reported again
+    const replaceItems = (items: vsdiag.WorkspaceDocumentDiagnosticReport[]) => {
+      if (!selectCssLanguageService()) return items
+      return items.map((item) => {
+        if (item.kind !== vsdiag.DocumentDiagnosticReportKind.full) return item
+        if (path.extname(item.uri.path) !== '.wxss') return item
+        return { ...item, items: [] }
+      })
+    }
+    const report = await next(resultIds, token, (chunk) => {
+      resultReporter(chunk && { items: replaceItems(chunk.items) })
+    })
+    return report && { items: replaceItems(report.items) }
+  },
+
   provideDocumentFormattingEdits(document, options, token, next) {
     if (path.extname(document.uri.path) === '.wxss') {
       const ls = selectCssLanguageService()
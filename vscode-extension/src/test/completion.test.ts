@@ -1,5 +1,5 @@
 import * as vscode from 'vscode'
-import { Env } from './env'
+import { Env, languageServer } from './env'
 
 const defWxmlCases = [
   {
@@ -92,6 +92,44 @@ const defWxmlTsCases = [
   },
 ]
 
+const resolveWxmlCases = [
+  {
+    name: 'attribute',
+    args: [new vscode.Position(0, 5), new vscode.Position(0, 6), new vscode.Position(2, 19)],
+  },
+]
+
+const resolveWxssCases = [
+  {
+    name: 'style-rule',
+    args: [new vscode.Position(1, 7), new vscode.Position(4, 5), new vscode.Position(11, 1)],
+  },
+]
+
+type CompletionItem = {
+  label: string
+  detail?: string
+  documentation?: string | { value: string }
+}
+
+// resolve every item with the server directly, since VS Code only resolves the items shown
+const resolveCompletionItems = async (uri: vscode.Uri, position: vscode.Position) => {
+  const api = languageServer()
+  const ret = (await api.request('textDocument/completion', {
+    textDocument: { uri: uri.toString() },
+    position: { line: position.line, character: position.character },
+  })) as { items: CompletionItem[] } | CompletionItem[] | null
+  const items = Array.isArray(ret) ? ret : (ret?.items ?? [])
+  const resolved = []
+  for (const item of items) {
+    const x = (await api.request('completionItem/resolve', item)) as CompletionItem
+    const documentation =
+      typeof x.documentation === 'string' ? x.documentation : x.documentation?.value
+    resolved.push({ label: x.label, detail: x.detail, documentation })
+  }
+  return resolved
+}
+
 suite('completion', function () {
   const env = new Env(this)
 
@@ -136,4 +174,22 @@ suite('completion', function () {
       }
     })
   })
+
+  test('resolve wxml', async function () {
+    await env.wxmlCasesWith(this, resolveWxmlCases, async (uri, list, expect) => {
+      await vscode.workspace.openTextDocument(uri)
+      for (const position of list) {
+        expect.snapshot(await resolveCompletionItems(uri, position))
+      }
+    })
+  })
+
+  test('resolve wxss', async function () {
+    await env.wxssCasesWith(this, resolveWxssCases, async (uri, list, expect) => {
+      await vscode.workspace.openTextDocument(uri)
+      for (const position of list) {
+        expect.snapshot(await resolveCompletionItems(uri, position))
+      }
+    })
+  })
 })
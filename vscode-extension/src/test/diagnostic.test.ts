@@ -1,5 +1,16 @@
 import * as vscode from 'vscode'
-import { Env } from './env'
+import { Env, languageServer } from './env'
+
+const componentCases = [
+  { name: 'property', args: null },
+  { name: 'handler', args: null },
+  { name: 'slot', args: null },
+  { name: 'generics', args: null },
+  { name: 'backend', args: null },
+  { name: 'placeholder', args: null, ext: 'json' },
+]
+
+type DocumentDiagnosticReport = { kind: string; resultId?: string; items?: unknown[] }
 
 suite('diagnostic', function () {
   const env = new Env(this)
@@ -30,4 +41,81 @@ suite('diagnostic', function () {
       expect.snapshot(ret)
     })
   })
+
+  test('component', async function () {
+    await env.casesWith(this, 'component', componentCases, 'wxml', async (uri, _args, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      await new Promise((resolve) => {
+        setTimeout(resolve, 200)
+      })
+      const ret = vscode.languages.getDiagnostics(uri)
+      expect.snapshot(ret)
+    })
+  })
+
+  test('pull', async function () {
+    const api = languageServer()
+    await env.casesWith(
+      this,
+      'component',
+      [{ name: 'slot', args: null }],
+      'wxml',
+      async (uri, _args, expect) => {
+        await vscode.workspace.openTextDocument(uri)
+        const textDocument = { uri: uri.toString() }
+        const first = (await api.request('textDocument/diagnostic', {
+          textDocument,
+        })) as DocumentDiagnosticReport
+        expect.snapshot({ kind: first.kind, items: first.items })
+        // nothing changed, so the previous result should be reused
+        const second = (await api.request('textDocument/diagnostic', {
+          textDocument,
+          previousResultId: first.resultId,
+        })) as DocumentDiagnosticReport
+        expect.snapshot({ kind: second.kind, sameResultId: second.resultId === first.resultId })
+      },
+    )
+  })
+
+  test('backend configuration', async function () {
+    const api = languageServer()
+    const config = () => vscode.workspace.getConfiguration('glass-easel-analyzer')
+    const pull = async (uri: vscode.Uri) => {
+      const ret = (await api.request('textDocument/diagnostic', {
+        textDocument: { uri: uri.toString() },
+      })) as DocumentDiagnosticReport
+      return { kind: ret.kind, items: ret.items }
+    }
+    try {
+      await env.casesWith(
+        this,
+        'backend-config',
+        [
+          { name: 'overlay', args: null },
+          { name: 'invalid', args: null },
+        ],
+        'toml',
+        async (uri, _args, expect) => {
+          const componentUri = vscode.Uri.joinPath(uri, '..', '..', 'component', 'backend.wxml')
+          await vscode.workspace.openTextDocument(componentUri)
+          await config().update(
+            'backendConfigurationOverlays',
+            [uri.fsPath],
+            vscode.ConfigurationTarget.Global,
+          )
+          await new Promise((resolve) => {
+            setTimeout(resolve, 500)
+          })
+          expect.snapshot(await pull(uri))
+          expect.snapshot(await pull(componentUri))
+        },
+      )
+    } finally {
+      await config().update(
+        'backendConfigurationOverlays',
+        undefined,
+        vscode.ConfigurationTarget.Global,
+      )
+    }
+  })
 })
@@ -3,6 +3,7 @@ import fs from 'node:fs'
 import * as vscode from 'vscode'
 import * as diff from 'diff'
 import chalk from 'chalk'
+import type { ExtensionApi } from '../extension'
 
 const wxmlCases = [
   'core-attribute',
@@ -41,6 +42,10 @@ const TEST_FIXTURE_DIR = path.resolve(EXTENSION_DIR, 'test-fixture')
 const SNAPSHOT_DIR = path.resolve(EXTENSION_DIR, 'test-snapshot')
 const OVERWRITE_SNAPSHOT = process.env.TEST_OVERWRITE_SNAPSHOT
 
+// send requests to the language server directly, for the requests not exposed by VS Code
+export const languageServer = () =>
+  vscode.extensions.getExtension<ExtensionApi>('wechat-miniprogram.glass-easel-analyzer')!.exports
+
 const normalizeTitle = (title: string) =>
   title
     .match(/[a-zA-Z0-9]+/g)!
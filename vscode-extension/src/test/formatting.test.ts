@@ -0,0 +1,161 @@
+import * as vscode from 'vscode'
+import { Env } from './env'
+
+const formattingOptions: vscode.FormattingOptions = { tabSize: 4, insertSpaces: true }
+
+const selectLine = (line: number) =>
+  new vscode.Range(new vscode.Position(line, 0), new vscode.Position(line, 100))
+
+const applyEdits = async (uri: vscode.Uri, edits: vscode.TextEdit[] | undefined) => {
+  const edit = new vscode.WorkspaceEdit()
+  edit.set(uri, edits ?? [])
+  await vscode.workspace.applyEdit(edit)
+  const doc = await vscode.workspace.openTextDocument(uri)
+  return doc.getText().split('\n')
+}
+
+const revert = async () => {
+  await vscode.commands.executeCommand('workbench.action.files.revert')
+}
+
+const formatTwice = async (uri: vscode.Uri, expect: { snapshot(actual: unknown): void }) => {
+  await vscode.commands.executeCommand('vscode.open', uri)
+  const edits = await vscode.commands.executeCommand<vscode.TextEdit[] | undefined>(
+    'vscode.executeFormatDocumentProvider',
+    uri,
+    formattingOptions,
+  )
+  expect.snapshot(await applyEdits(uri, edits))
+  // the formatted content should not be changed again
+  const ret = await vscode.commands.executeCommand<vscode.TextEdit[] | undefined>(
+    'vscode.executeFormatDocumentProvider',
+    uri,
+    formattingOptions,
+  )
+  expect.snapshot(ret ?? [])
+  await revert()
+}
+
+suite('formatting', function () {
+  const env = new Env(this)
+  const config = () => vscode.workspace.getConfiguration('glass-easel-analyzer')
+
+  // WXSS files are formatted by the CSS language service unless WXSS diagnostics are disabled
+  suiteSetup(async function () {
+    await config().update('wxssDiagnosticsMode', 'disabled', vscode.ConfigurationTarget.Global)
+  })
+
+  suiteTeardown(async function () {
+    await config().update('wxssDiagnosticsMode', undefined, vscode.ConfigurationTarget.Global)
+  })
+
+  test('wxml', async function () {
+    await env.casesWith(
+      this,
+      'formatting',
+      [{ name: 'element', args: null }],
+      'wxml',
+      async (uri, _args, expect) => {
+        await formatTwice(uri, expect)
+      },
+    )
+  })
+
+  test('wxss', async function () {
+    await env.casesWith(
+      this,
+      'formatting',
+      [
+        { name: 'style-rule', args: null },
+        { name: 'comment', args: null },
+      ],
+      'wxss',
+      async (uri, _args, expect) => {
+        await formatTwice(uri, expect)
+      },
+    )
+  })
+
+  test('wxss with options', async function () {
+    await env.casesWith(
+      this,
+      'formatting/options',
+      [
+        { name: 'hex-color', args: null },
+        { name: 'sort-properties', args: null },
+      ],
+      'wxss',
+      async (uri, _args, expect) => {
+        await formatTwice(uri, expect)
+      },
+    )
+  })
+
+  test('wxml range', async function () {
+    await env.casesWith(
+      this,
+      'formatting',
+      [{ name: 'range', args: selectLine(1) }],
+      'wxml',
+      async (uri, range, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const edits = await vscode.commands.executeCommand<vscode.TextEdit[] | undefined>(
+          'vscode.executeFormatRangeProvider',
+          uri,
+          range,
+          formattingOptions,
+        )
+        expect.snapshot(await applyEdits(uri, edits))
+        await revert()
+      },
+    )
+  })
+
+  test('wxss range', async function () {
+    await env.casesWith(
+      this,
+      'formatting',
+      [{ name: 'range', args: selectLine(1) }],
+      'wxss',
+      async (uri, range, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const edits = await vscode.commands.executeCommand<vscode.TextEdit[] | undefined>(
+          'vscode.executeFormatRangeProvider',
+          uri,
+          range,
+          formattingOptions,
+        )
+        expect.snapshot(await applyEdits(uri, edits))
+        await revert()
+      },
+    )
+  })
+
+  test('on type', async function () {
+    await env.casesWith(
+      this,
+      'formatting',
+      [
+        {
+          name: 'reindent',
+          args: [new vscode.Position(1, 6), new vscode.Position(2, 12), new vscode.Position(4, 9)],
+        },
+      ],
+      'wxml',
+      async (uri, list, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        for (const pos of list) {
+          const edits = await vscode.commands.executeCommand<vscode.TextEdit[] | undefined>(
+            'vscode.executeFormatOnTypeProvider',
+            uri,
+            pos,
+            '>',
+            formattingOptions,
+          )
+          expect.snapshot(await applyEdits(uri, edits))
+          await revert()
+        }
+      },
+    )
+  })
+})
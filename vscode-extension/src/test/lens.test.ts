@@ -0,0 +1,53 @@
+import * as vscode from 'vscode'
+import { Env } from './env'
+
+// the command arguments contain absolute paths, so only the titles are compared
+const codeLensSnapshot = (list: vscode.CodeLens[]) =>
+  list.map((x) => ({ range: x.range, title: x.command?.title, command: x.command?.command }))
+
+suite('code lens', function () {
+  const env = new Env(this)
+
+  test('component', async function () {
+    await env.casesWith(
+      this,
+      'component',
+      [{ name: 'item', args: null }],
+      'wxml',
+      async (uri, _args, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const ret = await vscode.commands.executeCommand<vscode.CodeLens[]>(
+          'vscode.executeCodeLensProvider',
+          uri,
+        )
+        expect.snapshot(codeLensSnapshot(ret))
+      },
+    )
+  })
+
+  test('wxml', async function () {
+    await env.wxmlCasesWith(this, [{ name: 'global2', args: null }], async (uri, _args, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      const ret = await vscode.commands.executeCommand<vscode.CodeLens[]>(
+        'vscode.executeCodeLensProvider',
+        uri,
+      )
+      expect.snapshot(codeLensSnapshot(ret))
+    })
+  })
+
+  test('wxss', async function () {
+    await env.wxssCasesWith(
+      this,
+      [{ name: 'style-rule', args: null }],
+      async (uri, _args, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const ret = await vscode.commands.executeCommand<vscode.CodeLens[]>(
+          'vscode.executeCodeLensProvider',
+          uri,
+        )
+        expect.snapshot(codeLensSnapshot(ret))
+      },
+    )
+  })
+})
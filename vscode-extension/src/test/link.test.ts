@@ -0,0 +1,29 @@
+import * as vscode from 'vscode'
+import { Env } from './env'
+
+const wxmlCases = [
+  { name: 'import', args: null },
+  { name: 'wxs', args: null },
+]
+
+const wxssCases = [{ name: 'import', args: null }]
+
+suite('document link', function () {
+  const env = new Env(this)
+
+  test('wxml', async function () {
+    await env.wxmlCasesWith(this, wxmlCases, async (uri, _args, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      const ret = await vscode.commands.executeCommand('vscode.executeLinkProvider', uri)
+      expect.snapshot(ret)
+    })
+  })
+
+  test('wxss', async function () {
+    await env.wxssCasesWith(this, wxssCases, async (uri, _args, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      const ret = await vscode.commands.executeCommand('vscode.executeLinkProvider', uri)
+      expect.snapshot(ret)
+    })
+  })
+})
@@ -0,0 +1,41 @@
+import * as vscode from 'vscode'
+import { Env, languageServer } from './env'
+
+const wxmlCases = [
+  {
+    name: 'wx-for',
+    args: [new vscode.Position(1, 11), new vscode.Position(0, 17), new vscode.Position(4, 15)],
+  },
+]
+
+const wxssCases = [
+  {
+    name: 'style-rule',
+    args: [new vscode.Position(5, 11), new vscode.Position(0, 8), new vscode.Position(19, 12)],
+  },
+]
+
+// VS Code merges the word and bracket ranges into the result, so the server is requested directly
+const selectionRanges = async (uri: vscode.Uri, positions: vscode.Position[]) =>
+  languageServer().request('textDocument/selectionRange', {
+    textDocument: { uri: uri.toString() },
+    positions: positions.map(({ line, character }) => ({ line, character })),
+  })
+
+suite('selection range', function () {
+  const env = new Env(this)
+
+  test('wxml', async function () {
+    await env.wxmlCasesWith(this, wxmlCases, async (uri, list, expect) => {
+      await vscode.workspace.openTextDocument(uri)
+      expect.snapshot(await selectionRanges(uri, list))
+    })
+  })
+
+  test('wxss', async function () {
+    await env.wxssCasesWith(this, wxssCases, async (uri, list, expect) => {
+      await vscode.workspace.openTextDocument(uri)
+      expect.snapshot(await selectionRanges(uri, list))
+    })
+  })
+})
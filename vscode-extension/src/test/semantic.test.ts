@@ -1,5 +1,11 @@
 import * as vscode from 'vscode'
-import { Env } from './env'
+import { Env, languageServer } from './env'
+
+type SemanticTokens = { resultId?: string; data: number[] }
+type SemanticTokensDelta = {
+  resultId?: string
+  edits: { start: number; deleteCount: number; data?: number[] }[]
+}
 
 suite('semantic tokens', function () {
   const env = new Env(this)
@@ -32,6 +38,14 @@ suite('semantic tokens', function () {
     })
   })
 
+  test('wxss functions (full)', async function () {
+    await env.wxssCasesWith(this, [{ name: 'function', args: null }], async (uri, _, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      const ret = await vscode.commands.executeCommand('vscode.provideDocumentSemanticTokens', uri)
+      expect.snapshot(ret)
+    })
+  })
+
   test('wxss (range)', async function () {
     await env.forEachWxssCase(this, async (uri, expect) => {
       await vscode.commands.executeCommand('vscode.open', uri)
@@ -43,4 +57,36 @@ suite('semantic tokens', function () {
       expect.snapshot(ret)
     })
   })
+
+  test('wxml (delta)', async function () {
+    const api = languageServer()
+    const cases = [{ name: 'semantic-delta', args: new vscode.Position(0, 5) }]
+    await env.wxmlCasesWith(this, cases, async (uri, pos, expect) => {
+      // not shown in an editor, so the cached result is not replaced by the editor requests
+      const doc = await vscode.workspace.openTextDocument(uri)
+      const textDocument = { uri: uri.toString() }
+      const old = (await api.request('textDocument/semanticTokens/full', {
+        textDocument,
+      })) as SemanticTokens
+      const insert = new vscode.WorkspaceEdit()
+      insert.insert(uri, pos, ' id="c"')
+      await vscode.workspace.applyEdit(insert)
+      const delta = (await api.request('textDocument/semanticTokens/full/delta', {
+        textDocument,
+        previousResultId: old.resultId,
+      })) as SemanticTokensDelta
+      expect.snapshot(delta.edits)
+      const full = (await api.request('textDocument/semanticTokens/full', {
+        textDocument,
+      })) as SemanticTokens
+      const data = old.data.slice()
+      for (const edit of delta.edits.slice().sort((a, b) => b.start - a.start)) {
+        data.splice(edit.start, edit.deleteCount, ...(edit.data ?? []))
+      }
+      expect.snapshot(data.join(',') === full.data.join(','))
+      // discard the change without touching the fixture on disk
+      await vscode.window.showTextDocument(doc)
+      await vscode.commands.executeCommand('workbench.action.files.revert')
+    })
+  })
 })
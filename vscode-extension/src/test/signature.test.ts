@@ -0,0 +1,56 @@
+import * as vscode from 'vscode'
+import { Env } from './env'
+
+const wxmlCases = [
+  {
+    name: 'signature',
+    args: [
+      new vscode.Position(6, 13),
+      new vscode.Position(6, 21),
+      new vscode.Position(6, 28),
+      new vscode.Position(6, 31),
+      new vscode.Position(7, 18),
+      new vscode.Position(7, 22),
+      new vscode.Position(7, 32),
+    ],
+  },
+]
+
+const wxssCases = [
+  {
+    name: 'function',
+    args: [new vscode.Position(1, 17), new vscode.Position(1, 34), new vscode.Position(1, 36)],
+  },
+]
+
+suite('signature help', function () {
+  const env = new Env(this)
+
+  test('wxml', async function () {
+    await env.wxmlCasesWith(this, wxmlCases, async (uri, list, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      for (const position of list) {
+        const ret = await vscode.commands.executeCommand(
+          'vscode.executeSignatureHelpProvider',
+          uri,
+          position,
+        )
+        expect.snapshot(ret)
+      }
+    })
+  })
+
+  test('wxss', async function () {
+    await env.wxssCasesWith(this, wxssCases, async (uri, list, expect) => {
+      await vscode.commands.executeCommand('vscode.open', uri)
+      for (const position of list) {
+        const ret = await vscode.commands.executeCommand(
+          'vscode.executeSignatureHelpProvider',
+          uri,
+          position,
+        )
+        expect.snapshot(ret)
+      }
+    })
+  })
+})
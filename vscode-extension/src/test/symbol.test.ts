@@ -20,3 +20,60 @@ suite('document symbol', function () {
     })
   })
 })
+
+// the symbols of other languages in the workspace are ignored
+const isAnalyzedFile = (x: vscode.SymbolInformation) => !/\.[jt]s$/.test(x.location.uri.path)
+
+suite('workspace symbol', function () {
+  const env = new Env(this)
+
+  test('component', async function () {
+    await env.casesWith(
+      this,
+      'component',
+      [{ name: 'slot', args: 'item', ext: 'json' }],
+      'json',
+      async (uri, query, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const ret = await vscode.commands.executeCommand<vscode.SymbolInformation[]>(
+          'vscode.executeWorkspaceSymbolProvider',
+          query,
+        )
+        expect.snapshot(ret.filter(isAnalyzedFile))
+      },
+    )
+  })
+
+  test('wxml', async function () {
+    await env.wxmlCasesWith(
+      this,
+      [{ name: 'global', args: 'tmpl' }],
+      async (uri, query, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const ret = await vscode.commands.executeCommand<vscode.SymbolInformation[]>(
+          'vscode.executeWorkspaceSymbolProvider',
+          query,
+        )
+        expect.snapshot(ret.filter(isAnalyzedFile))
+      },
+    )
+  })
+
+  test('wxss', async function () {
+    await env.wxssCasesWith(
+      this,
+      [
+        { name: 'keyframes', args: '@key' },
+        { name: 'style-rule', args: '#' },
+      ],
+      async (uri, query, expect) => {
+        await vscode.commands.executeCommand('vscode.open', uri)
+        const ret = await vscode.commands.executeCommand<vscode.SymbolInformation[]>(
+          'vscode.executeWorkspaceSymbolProvider',
+          query,
+        )
+        expect.snapshot(ret.filter(isAnalyzedFile))
+      },
+    )
+  })
+})
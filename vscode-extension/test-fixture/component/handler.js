@@ -0,0 +1,4 @@
+Page({
+  onTap() {},
+  onChange() {},
+})
@@ -0,0 +1,13 @@
+Component({
+  options: {
+    multipleSlots: true,
+  },
+  properties: {
+    label: String,
+  },
+  methods: {
+    onTap() {
+      this.triggerEvent('change', {})
+    },
+  },
+})
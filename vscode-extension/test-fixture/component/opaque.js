@@ -0,0 +1,7 @@
+const definition = {
+  properties: {
+    label: String,
+  },
+}
+
+Component(definition)
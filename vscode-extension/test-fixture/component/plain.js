@@ -0,0 +1,5 @@
+Component({
+  options: {
+    multipleSlots: false,
+  },
+})
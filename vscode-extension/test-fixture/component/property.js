@@ -0,0 +1,5 @@
+Component({
+  methods: {
+    onChange() {},
+  },
+})